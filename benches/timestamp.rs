@@ -31,6 +31,13 @@ fn criterion_benchmark(c: &mut Criterion) {
         b.iter(|| ts.format_nanoseconds());
     });
 
+    format_group.bench_function("iso8601_spec", |b| {
+        let ts = black_box(Timestamp::now_utc());
+        let spec = black_box(iso8601_timestamp::FormatSpec::DEFAULT);
+
+        b.iter(|| ts.format_spec(&spec));
+    });
+
     format_group.bench_function("is8601_slow", |b| {
         let ts = black_box(Utc::now().naive_utc());
 
@@ -92,6 +99,34 @@ fn criterion_benchmark(c: &mut Criterion) {
         b.iter(|| iso8601::datetime(ts).unwrap());
     });
 
+    let now = Timestamp::now_utc();
+    let column: Vec<String> = (0..1024)
+        .map(|i| {
+            (now + time::Duration::milliseconds(i * 1234))
+                .format()
+                .to_string()
+        })
+        .collect();
+    let column: Vec<&str> = column.iter().map(|s| s.as_str()).collect();
+
+    parse_group.bench_function("iso8601_column_loop", |b| {
+        let column = black_box(&column);
+        let mut out = vec![None; column.len()];
+
+        b.iter(|| {
+            for (ts, out) in column.iter().zip(&mut out) {
+                *out = Timestamp::parse(ts);
+            }
+        });
+    });
+
+    parse_group.bench_function("iso8601_column_batch", |b| {
+        let column = black_box(&column);
+        let mut out = vec![None; column.len()];
+
+        b.iter(|| Timestamp::parse_batch(column, &mut out));
+    });
+
     parse_group.finish();
 }
 
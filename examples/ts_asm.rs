@@ -18,6 +18,12 @@ pub fn parse_iso8601(ts: &str) -> Option<Timestamp> {
     Timestamp::parse(ts)
 }
 
+#[inline(never)]
+#[unsafe(no_mangle)]
+pub fn parse_iso8601_batch(ts: &[&str], out: &mut [Option<Timestamp>]) {
+    Timestamp::parse_batch(ts, out)
+}
+
 #[inline(never)]
 #[unsafe(no_mangle)]
 pub fn to_calendar_date(ts: Timestamp) -> (i32, Month, u8) {
@@ -0,0 +1,352 @@
+//! [RFC 9557](https://www.rfc-editor.org/rfc/rfc9557) bracketed suffixes, as sent by the JavaScript
+//! Temporal API, such as `2021-10-17T02:03:01+02:00[Europe/Paris][u-ca=gregory]`.
+//!
+//! The first annotation may name a time zone, either from the IANA database or as a numeric offset,
+//! and any that follow are `key=value` tags. A leading `!` marks an annotation as critical,
+//! meaning that an application which cannot act upon it should reject the timestamp.
+
+use core::fmt;
+
+use crate::error::{ParseError, ParseErrorKind as K};
+use crate::parsed::ParsedOffset;
+use crate::ts_str::{IsValidFormat, TimestampStr};
+
+/// A single bracketed annotation, such as `[Europe/Paris]` or `[!u-ca=gregory]`.
+///
+/// Annotations built by hand are written out as-is, without being checked against the RFC 9557 grammar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Annotation<'a> {
+    /// Whether the annotation was marked with `!`, so must not be ignored
+    pub critical: bool,
+    /// The key of a tag, or `None` for a time zone
+    pub key: Option<&'a str>,
+    /// The time zone name or offset, or the value of a tag
+    pub value: &'a str,
+}
+
+impl<'a> Annotation<'a> {
+    /// A time zone annotation, such as `Europe/Paris` or `+02:00`.
+    #[inline]
+    #[must_use]
+    pub const fn time_zone(name: &'a str) -> Self {
+        Annotation {
+            critical: false,
+            key: None,
+            value: name,
+        }
+    }
+
+    /// A `key=value` tag, such as `u-ca=gregory`.
+    #[inline]
+    #[must_use]
+    pub const fn tag(key: &'a str, value: &'a str) -> Self {
+        Annotation {
+            critical: false,
+            key: Some(key),
+            value,
+        }
+    }
+
+    /// Marks the annotation as critical, written with a leading `!`.
+    #[inline]
+    #[must_use]
+    pub const fn critical(mut self) -> Self {
+        self.critical = true;
+        self
+    }
+
+    /// Returns `true` if this annotation names a time zone rather than being a tag.
+    #[inline]
+    #[must_use]
+    pub const fn is_time_zone(&self) -> bool {
+        self.key.is_none()
+    }
+
+    /// Splits a single annotation that has already been checked, without its brackets.
+    fn from_validated(mut s: &'a str) -> Self {
+        let critical = s.starts_with('!');
+        if critical {
+            s = &s[1..];
+        }
+
+        match s.split_once('=') {
+            Some((key, value)) => Annotation {
+                critical,
+                key: Some(key),
+                value,
+            },
+            None => Annotation {
+                critical,
+                key: None,
+                value: s,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Annotation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+
+        if self.critical {
+            f.write_str("!")?;
+        }
+
+        if let Some(key) = self.key {
+            f.write_str(key)?;
+            f.write_str("=")?;
+        }
+
+        f.write_str(self.value)?;
+        f.write_str("]")
+    }
+}
+
+/// The annotations following a timestamp, as returned by
+/// [`Timestamp::parse_with_annotations`](crate::Timestamp::parse_with_annotations).
+///
+/// ```rust
+/// # use iso8601_timestamp::{Annotation, Timestamp};
+/// let (_, _, annotations) =
+///     Timestamp::parse_with_annotations("2021-10-17T02:03:01+02:00[Europe/Paris][!u-ca=gregory]").unwrap();
+///
+/// assert_eq!(annotations.time_zone(), Some(Annotation::time_zone("Europe/Paris")));
+/// assert_eq!(annotations.get("u-ca"), Some(Annotation::tag("u-ca", "gregory").critical()));
+/// assert_eq!(annotations.iter().count(), 2);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Annotations<'a>(&'a str);
+
+impl<'a> Annotations<'a> {
+    /// Wraps the suffix of a timestamp that has already been checked by [`validate`].
+    #[inline]
+    pub(crate) const fn new(suffix: &'a str) -> Self {
+        Annotations(suffix)
+    }
+
+    /// The annotations exactly as they appeared in the input, brackets included.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Returns `true` if there were no annotations.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The time zone annotation, which can only be the first.
+    #[inline]
+    #[must_use]
+    pub fn time_zone(&self) -> Option<Annotation<'a>> {
+        self.iter().next().filter(Annotation::is_time_zone)
+    }
+
+    /// The first tag with the given key. RFC 9557 asks that any repeats of an elective tag
+    /// after the first are ignored.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<Annotation<'a>> {
+        self.iter().find(|annotation| annotation.key == Some(key))
+    }
+
+    /// Iterates over every annotation in order.
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> AnnotationIter<'a> {
+        AnnotationIter(self.0)
+    }
+}
+
+impl<'a> IntoIterator for Annotations<'a> {
+    type Item = Annotation<'a>;
+    type IntoIter = AnnotationIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Display for Annotations<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// Iterator over [`Annotations`].
+#[derive(Debug, Clone)]
+pub struct AnnotationIter<'a>(&'a str);
+
+impl<'a> Iterator for AnnotationIter<'a> {
+    type Item = Annotation<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (annotation, rest) = self.0.strip_prefix('[')?.split_once(']')?;
+        self.0 = rest;
+        Some(Annotation::from_validated(annotation))
+    }
+}
+
+impl core::iter::FusedIterator for AnnotationIter<'_> {}
+
+/// A formatted timestamp followed by annotations, as returned by [`TimestampStr::with_annotations`].
+#[derive(Debug, Clone, Copy)]
+pub struct Annotated<S: IsValidFormat, A> {
+    ts: TimestampStr<S>,
+    annotations: A,
+}
+
+impl<S: IsValidFormat> TimestampStr<S> {
+    /// Appends RFC 9557 annotations to the formatted timestamp, for clients such as the JavaScript Temporal API.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Annotation, ParsedOffset, Timestamp, typenum as t};
+    /// let ts = Timestamp::parse("2021-10-17T00:03:01Z").unwrap();
+    /// let offset = ParsedOffset::Fixed(time::macros::offset!(+02:00));
+    /// let annotations = [Annotation::time_zone("Europe/Paris"), Annotation::tag("u-ca", "gregory")];
+    ///
+    /// assert_eq!(
+    ///     ts.format_at_offset::<t::U0>(offset).with_annotations(annotations).to_string(),
+    ///     "2021-10-17T02:03:01+02:00[Europe/Paris][u-ca=gregory]",
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_annotations<'a, A>(self, annotations: A) -> Annotated<S, A>
+    where
+        A: IntoIterator<Item = Annotation<'a>> + Clone,
+    {
+        Annotated {
+            ts: self,
+            annotations,
+        }
+    }
+}
+
+impl<'a, S: IsValidFormat, A> fmt::Display for Annotated<S, A>
+where
+    A: IntoIterator<Item = Annotation<'a>> + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.ts)?;
+
+        for annotation in self.annotations.clone() {
+            fmt::Display::fmt(&annotation, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks the annotations in `b[start..]` against the RFC 9557 grammar, with errors at offsets into `b`.
+///
+/// A critical time zone given as an offset must also agree with the offset of the timestamp, and
+/// other critical annotations are only accepted if they are `kept` for the caller to act upon.
+pub(crate) fn validate(b: &[u8], start: usize, offset: ParsedOffset, kept: bool) -> Result<(), ParseError> {
+    let mut pos = start;
+    let mut first = true;
+
+    while pos < b.len() {
+        let open = pos;
+
+        if b[pos] != b'[' {
+            return Err(ParseError::new(pos, K::TrailingCharacters));
+        }
+
+        pos += 1;
+
+        let critical = b.get(pos) == Some(&b'!');
+        pos += critical as usize;
+
+        let Some(len) = b[pos..].iter().position(|&c| c == b']') else {
+            return Err(ParseError::new(b.len(), K::UnexpectedEnd));
+        };
+
+        let body = &b[pos..pos + len];
+
+        let valid = match body.iter().position(|&c| c == b'=') {
+            Some(eq) => (kept || !critical) && is_key(&body[..eq]) && is_values(&body[eq + 1..]),
+            // only the first annotation may be a time zone
+            None if first => match numeric_offset(body) {
+                Some(seconds) => {
+                    !critical || !matches!(offset, ParsedOffset::Fixed(o) if o.whole_seconds() != seconds)
+                }
+                None => (kept || !critical) && is_time_zone_name(body),
+            },
+            None => false,
+        };
+
+        if !valid {
+            return Err(ParseError::new(open, K::InvalidAnnotation));
+        }
+
+        pos += len + 1;
+        first = false;
+    }
+
+    Ok(())
+}
+
+/// `[a-z_][a-z0-9_-]*`
+fn is_key(key: &[u8]) -> bool {
+    match key.split_first() {
+        Some((first, rest)) => {
+            matches!(first, b'a'..=b'z' | b'_')
+                && rest
+                    .iter()
+                    .all(|c| matches!(c, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-'))
+        }
+        None => false,
+    }
+}
+
+/// One or more alphanumeric values separated by `-`
+fn is_values(values: &[u8]) -> bool {
+    values
+        .split(|&c| c == b'-')
+        .all(|value| !value.is_empty() && value.iter().all(u8::is_ascii_alphanumeric))
+}
+
+/// Parts of up to 14 characters separated by `/`, such as `America/Argentina/ComodRivadavia`
+fn is_time_zone_name(name: &[u8]) -> bool {
+    name.split(|&c| c == b'/').all(|part| match part.split_first() {
+        Some((first, rest)) => {
+            part.len() <= 14
+                && part != b"."
+                && part != b".."
+                && matches!(first, b'a'..=b'z' | b'A'..=b'Z' | b'.' | b'_')
+                && rest
+                    .iter()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, b'.' | b'_' | b'-' | b'+'))
+        }
+        None => false,
+    })
+}
+
+/// `±hh:mm`, as seconds east of UTC
+fn numeric_offset(offset: &[u8]) -> Option<i32> {
+    let [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] = *offset else {
+        return None;
+    };
+
+    let digits = [h1, h2, m1, m2];
+    if !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+
+    let [h1, h2, m1, m2] = digits.map(|c| (c - b'0') as i32);
+    let (hours, minutes) = (h1 * 10 + h2, m1 * 10 + m2);
+
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+
+    let seconds = (hours * 60 + minutes) * 60;
+
+    Some(if sign == b'-' { -seconds } else { seconds })
+}
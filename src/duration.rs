@@ -0,0 +1,524 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+use time::{Date, Duration, Month, PrimitiveDateTime};
+
+use crate::error::{ParseError, ParseErrorKind as K};
+use crate::ts_str::{DurationFormatString, IsValidFormat, TimestampStr};
+
+/// An ISO8601 duration, such as `P1Y2M10DT2H30M` or `P3W`.
+///
+/// Nominal components (years, months, weeks and days) are kept apart from the exact components
+/// (hours, minutes and seconds), as their length depends on where in the calendar they are applied.
+/// Adding a duration to a [`Timestamp`](crate::Timestamp) first adds the years and months, keeping the day
+/// within the resulting month, then the weeks and days, and finally the exact components.
+///
+/// ```rust
+/// # use iso8601_timestamp::{IsoDuration, Timestamp};
+/// let duration = IsoDuration::parse("P1M1DT1H").unwrap();
+/// let ts = Timestamp::parse("2021-01-31T00:00:00Z").unwrap();
+///
+/// assert_eq!(ts + duration, Timestamp::parse("2021-03-01T01:00:00Z").unwrap());
+/// assert_eq!(duration.format(), "P1M1DT1H");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IsoDuration {
+    /// Whether the whole duration is negative, written with a leading `-`
+    pub negative: bool,
+    /// Nominal years, `nY`
+    pub years: u32,
+    /// Nominal months, `nM` before any `T`
+    pub months: u32,
+    /// Nominal weeks, `nW`
+    pub weeks: u32,
+    /// Nominal days, `nD`
+    pub days: u32,
+    /// Exact hours, `nH`
+    pub hours: u32,
+    /// Exact minutes, `nM` after the `T`
+    pub minutes: u32,
+    /// Exact seconds, `nS`
+    pub seconds: u32,
+    /// Fractional seconds, written as decimal places of `nS`. Whole seconds carry into [`IsoDuration::seconds`].
+    pub nanoseconds: u32,
+}
+
+impl IsoDuration {
+    /// A duration of zero, `PT0S`
+    pub const ZERO: Self = IsoDuration {
+        negative: false,
+        years: 0,
+        months: 0,
+        weeks: 0,
+        days: 0,
+        hours: 0,
+        minutes: 0,
+        seconds: 0,
+        nanoseconds: 0,
+    };
+
+    /// Returns `true` if every component is zero.
+    #[inline]
+    #[must_use]
+    pub const fn is_zero(&self) -> bool {
+        (self.years | self.months | self.weeks | self.days) == 0
+            && (self.hours | self.minutes | self.seconds | self.nanoseconds) == 0
+    }
+
+    /// Returns `true` if there are no nominal components, so the duration has a fixed length.
+    #[inline]
+    #[must_use]
+    pub const fn is_exact(&self) -> bool {
+        (self.years | self.months | self.weeks | self.days) == 0
+    }
+
+    /// The exact components as a [`Duration`], ignoring any nominal components.
+    #[must_use]
+    pub fn exact(&self) -> Duration {
+        let seconds = self.hours as i64 * 3600 + self.minutes as i64 * 60 + self.seconds as i64;
+        let duration = Duration::seconds(seconds) + Duration::nanoseconds(self.nanoseconds as i64);
+
+        match self.negative {
+            true => -duration,
+            false => duration,
+        }
+    }
+
+    /// Parse an ISO8601 duration, such as `P1Y2M10DT2H30M`, `PT0.5S` or `-P3W`.
+    #[inline(never)]
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        parse_duration(s.as_bytes()).ok()
+    }
+
+    /// Parse an ISO8601 duration, like [`IsoDuration::parse`], but returns a [`ParseError`]
+    /// describing why and where parsing failed.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{IsoDuration, ParseErrorKind};
+    /// let err = IsoDuration::try_parse("P1DT").unwrap_err();
+    ///
+    /// assert_eq!(err.kind(), ParseErrorKind::UnexpectedEnd);
+    /// assert_eq!(err.offset(), 4);
+    /// ```
+    #[inline(never)]
+    pub fn try_parse(s: &str) -> Result<Self, ParseError> {
+        parse_duration(s.as_bytes())
+    }
+
+    /// Format the duration without allocating, omitting zero components and trailing fractional zeroes.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::IsoDuration;
+    /// let duration = IsoDuration { weeks: 3, seconds: 1, nanoseconds: 500_000_000, ..IsoDuration::ZERO };
+    ///
+    /// assert_eq!(duration.format(), "P3WT1.5S");
+    /// assert_eq!(IsoDuration::ZERO.format(), "PT0S");
+    /// ```
+    #[must_use]
+    pub fn format(&self) -> TimestampStr<DurationFormatString> {
+        let mut value: <DurationFormatString as IsValidFormat>::Storage = Default::default();
+
+        let len = write_duration(&mut value.buf, self);
+        value.len = len as u8;
+
+        TimestampStr(value)
+    }
+
+    /// Computes `dt + self`, returning `None` if an overflow occurred.
+    #[inline]
+    #[must_use]
+    pub fn checked_add_to(self, dt: PrimitiveDateTime) -> Option<PrimitiveDateTime> {
+        self.checked_add_times_to(dt, 1)
+    }
+
+    /// Computes `dt + self * n`, scaling each component before adding it, so that repeatedly
+    /// stepping by a month from the 31st doesn't drift to the 28th.
+    pub(crate) fn checked_add_times_to(self, dt: PrimitiveDateTime, n: i64) -> Option<PrimitiveDateTime> {
+        let n = if self.negative { n.checked_neg()? } else { n };
+
+        let months = (self.years as i64 * 12 + self.months as i64).checked_mul(n)?;
+        let days = (self.weeks as i64 * 7 + self.days as i64).checked_mul(n)?;
+
+        let nanos = self.hours as i128 * 3_600_000_000_000
+            + self.minutes as i128 * 60_000_000_000
+            + self.seconds as i128 * 1_000_000_000
+            + self.nanoseconds as i128;
+        let nanos = nanos.checked_mul(n as i128)?;
+
+        let exact = Duration::new(
+            i64::try_from(nanos / 1_000_000_000).ok()?,
+            (nanos % 1_000_000_000) as i32,
+        );
+
+        let mut date = dt.date();
+
+        if months != 0 {
+            date = add_months(date, months)?;
+        }
+
+        if days != 0 {
+            date = date.checked_add(Duration::seconds(days.checked_mul(86_400)?))?;
+        }
+
+        PrimitiveDateTime::new(date, dt.time()).checked_add(exact)
+    }
+}
+
+/// Adds calendar months, keeping the day within the resulting month, so `01-31 + P1M` is the end of February.
+fn add_months(date: Date, months: i64) -> Option<Date> {
+    let (year, month, day) = date.to_calendar_date();
+
+    let months = (year as i64 * 12 + (month as i64 - 1)).checked_add(months)?;
+    let year = i32::try_from(months.div_euclid(12)).ok()?;
+    let month = Month::try_from(months.rem_euclid(12) as u8 + 1).ok()?;
+
+    Date::from_calendar_date(year, month, day.min(month.length(year))).ok()
+}
+
+impl Neg for IsoDuration {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        IsoDuration {
+            negative: !self.negative,
+            ..self
+        }
+    }
+}
+
+impl Add<IsoDuration> for PrimitiveDateTime {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if the result is out of range, like adding a [`Duration`].
+    #[inline]
+    fn add(self, rhs: IsoDuration) -> Self::Output {
+        rhs.checked_add_to(self).expect("resulting value is out of range")
+    }
+}
+
+impl Sub<IsoDuration> for PrimitiveDateTime {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if the result is out of range, like subtracting a [`Duration`].
+    #[inline]
+    fn sub(self, rhs: IsoDuration) -> Self::Output {
+        (-rhs)
+            .checked_add_to(self)
+            .expect("resulting value is out of range")
+    }
+}
+
+impl AddAssign<IsoDuration> for PrimitiveDateTime {
+    #[inline]
+    fn add_assign(&mut self, rhs: IsoDuration) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<IsoDuration> for PrimitiveDateTime {
+    #[inline]
+    fn sub_assign(&mut self, rhs: IsoDuration) {
+        *self = *self - rhs;
+    }
+}
+
+pub(crate) fn write_duration(buf: &mut [u8], d: &IsoDuration) -> usize {
+    let mut pos = 0;
+
+    // writes `value` followed by `designator`, returning the new position
+    fn component(buf: &mut [u8], mut pos: usize, value: u32, designator: u8) -> usize {
+        pos += write_u64(&mut buf[pos..], value as u64);
+        buf[pos] = designator;
+        pos + 1
+    }
+
+    if d.negative && !d.is_zero() {
+        buf[pos] = b'-';
+        pos += 1;
+    }
+
+    buf[pos] = b'P';
+    pos += 1;
+
+    let seconds = d.seconds as u64 + (d.nanoseconds / 1_000_000_000) as u64;
+    let nanoseconds = d.nanoseconds % 1_000_000_000;
+
+    for (value, designator) in [(d.years, b'Y'), (d.months, b'M'), (d.weeks, b'W'), (d.days, b'D')] {
+        if value != 0 {
+            pos = component(buf, pos, value, designator);
+        }
+    }
+
+    let has_seconds = seconds != 0 || nanoseconds != 0 || d.is_zero();
+
+    if d.hours != 0 || d.minutes != 0 || has_seconds {
+        buf[pos] = b'T';
+        pos += 1;
+
+        for (value, designator) in [(d.hours, b'H'), (d.minutes, b'M')] {
+            if value != 0 {
+                pos = component(buf, pos, value, designator);
+            }
+        }
+
+        if has_seconds {
+            pos += write_u64(&mut buf[pos..], seconds);
+
+            if nanoseconds != 0 {
+                buf[pos] = b'.';
+                pos += 1;
+
+                // write all 9 digits, then trim trailing zeroes
+                let mut n = nanoseconds;
+                for i in (0..9).rev() {
+                    buf[pos + i] = b'0' + (n % 10) as u8;
+                    n /= 10;
+                }
+
+                let mut digits = 9;
+                while buf[pos + digits - 1] == b'0' {
+                    digits -= 1;
+                }
+                pos += digits;
+            }
+
+            buf[pos] = b'S';
+            pos += 1;
+        }
+    }
+
+    pos
+}
+
+pub(crate) fn write_u64(buf: &mut [u8], mut value: u64) -> usize {
+    let mut digits = [0u8; 20];
+    let mut len = 0;
+
+    loop {
+        digits[len] = b'0' + (value % 10) as u8;
+        value /= 10;
+        len += 1;
+
+        if value == 0 {
+            break;
+        }
+    }
+
+    for (dst, src) in buf.iter_mut().zip(digits[..len].iter().rev()) {
+        *dst = *src;
+    }
+
+    len
+}
+
+/// Parses `[±]PnYnMnWnDTnHnMnS`, where any component may be omitted but at least one must be present,
+/// and only the last component may have a decimal fraction.
+pub(crate) fn parse_duration(b: &[u8]) -> Result<IsoDuration, ParseError> {
+    let mut d = IsoDuration::ZERO;
+    let mut pos = 0;
+
+    match b.first() {
+        None => return Err(ParseError::new(0, K::Empty)),
+        Some(b'-') => (d.negative, pos) = (true, 1),
+        Some(b'+') => pos = 1,
+        _ => {}
+    }
+
+    match b.get(pos) {
+        Some(b'P' | b'p') => pos += 1,
+        _ => return Err(unexpected(b, pos)),
+    }
+
+    // designators in the order they must appear, where `T` separates the date and time
+    const DATE: &[u8] = b"YMWD";
+    const TIME: &[u8] = b"HMS";
+
+    let mut in_time = false;
+    let mut next = 0; // index of the next designator allowed within the current part
+    let mut any = false;
+    let mut fraction_seen = false;
+
+    loop {
+        let start = pos;
+
+        match b.get(pos) {
+            None if any && !(in_time && next == 0) => break,
+            None => return Err(ParseError::new(b.len(), K::UnexpectedEnd)),
+
+            Some(b'T' | b't') if !in_time => {
+                in_time = true;
+                next = 0;
+                pos += 1;
+                continue;
+            }
+            Some(b'0'..=b'9') if !fraction_seen => {}
+            Some(_) => return Err(ParseError::new(pos, K::UnexpectedCharacter)),
+        }
+
+        // integer part
+        let mut value: u64 = 0;
+        while let Some(c @ b'0'..=b'9') = b.get(pos) {
+            value = value * 10 + (c - b'0') as u64;
+            if value > u32::MAX as u64 {
+                return Err(ParseError::new(start, K::OutOfRange));
+            }
+            pos += 1;
+        }
+
+        // optional decimal fraction, as billionths
+        let mut fraction = 0u64;
+        if let Some(b'.' | b',') = b.get(pos) {
+            pos += 1;
+
+            let digits_start = pos;
+            let mut scale = 100_000_000;
+            while let Some(c @ b'0'..=b'9') = b.get(pos) {
+                fraction += (c - b'0') as u64 * scale;
+                scale /= 10;
+                pos += 1;
+            }
+
+            if pos == digits_start {
+                return Err(unexpected(b, pos));
+            }
+
+            fraction_seen = true;
+        }
+
+        let part = if in_time { TIME } else { DATE };
+
+        let designator = match b.get(pos) {
+            Some(c) => c.to_ascii_uppercase(),
+            None => return Err(ParseError::new(b.len(), K::UnexpectedEnd)),
+        };
+
+        let Some(index) = part[next..].iter().position(|&c| c == designator) else {
+            return Err(ParseError::new(pos, K::UnexpectedCharacter));
+        };
+        let index = next + index;
+
+        // nominal units have no exact length to carry a fraction into
+        if fraction != 0 && !in_time {
+            return Err(ParseError::new(start, K::InvalidFraction));
+        }
+
+        // only the last component may have a fraction
+        if fraction_seen && pos + 1 != b.len() {
+            return Err(ParseError::new(pos + 1, K::TrailingCharacters));
+        }
+
+        let value = value as u32;
+
+        match (in_time, part[index]) {
+            (false, b'Y') => d.years = value,
+            (false, b'M') => d.months = value,
+            (false, b'W') => d.weeks = value,
+            (false, _) => d.days = value,
+            (true, b'H') => {
+                // fractional hours carry into exact minutes and seconds
+                let nanos = fraction * 3600;
+                d.hours = value;
+                d.minutes = (nanos / 60_000_000_000) as u32;
+                d.seconds = (nanos / 1_000_000_000 % 60) as u32;
+                d.nanoseconds = (nanos % 1_000_000_000) as u32;
+            }
+            (true, b'M') => {
+                let nanos = fraction * 60;
+                d.minutes = value;
+                d.seconds = (nanos / 1_000_000_000) as u32;
+                d.nanoseconds = (nanos % 1_000_000_000) as u32;
+            }
+            (true, _) => {
+                d.seconds = value;
+                d.nanoseconds = fraction as u32;
+            }
+        }
+
+        pos += 1;
+        next = index + 1;
+        any = true;
+    }
+
+    Ok(d)
+}
+
+#[cold]
+#[inline(never)]
+fn unexpected(b: &[u8], pos: usize) -> ParseError {
+    match pos < b.len() {
+        true => ParseError::new(pos, K::UnexpectedCharacter),
+        false => ParseError::new(b.len(), K::UnexpectedEnd),
+    }
+}
+
+impl core::str::FromStr for IsoDuration {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IsoDuration::try_parse(s)
+    }
+}
+
+impl fmt::Display for IsoDuration {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format())
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde_core::de::{Deserialize, Deserializer, Error, Visitor};
+    use serde_core::ser::{Serialize, Serializer};
+
+    use super::IsoDuration;
+
+    impl Serialize for IsoDuration {
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.format().serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for IsoDuration {
+        #[inline]
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            use core::fmt;
+
+            struct DurationVisitor;
+
+            impl Visitor<'_> for DurationVisitor {
+                type Value = IsoDuration;
+
+                #[inline]
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("an ISO8601 duration")
+                }
+
+                #[inline]
+                fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where
+                    E: Error,
+                {
+                    IsoDuration::try_parse(v).map_err(E::custom)
+                }
+            }
+
+            deserializer.deserialize_str(DurationVisitor)
+        }
+    }
+}
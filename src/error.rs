@@ -0,0 +1,116 @@
+use core::fmt;
+
+/// The kind of failure encountered while parsing a timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The input was empty.
+    Empty,
+    /// The input ended before the timestamp was complete.
+    UnexpectedEnd,
+    /// A non-numeric character was found where a digit was expected.
+    InvalidDigit,
+    /// An unexpected character was found, such as an unknown separator.
+    UnexpectedCharacter,
+    /// The month was not within `1..=12`.
+    MonthOutOfRange,
+    /// The day is not valid for the given year and month.
+    InvalidDayForMonth,
+    /// The day of the year was not valid for the given year.
+    OrdinalOutOfRange,
+    /// The week was not valid for the given week-numbering year.
+    WeekOutOfRange,
+    /// The day of the week was not within `1..=7`.
+    WeekdayOutOfRange,
+    /// The named day of the week did not match the date.
+    WeekdayMismatch,
+    /// The hour was not within `0..=23`.
+    HourOutOfRange,
+    /// The minute was not within `0..=59`.
+    MinuteOutOfRange,
+    /// The second was not within `0..=60`.
+    SecondOutOfRange,
+    /// The fractional seconds had too few or too many digits.
+    InvalidFraction,
+    /// The UTC offset was malformed or out of range.
+    InvalidOffset,
+    /// Extra characters were found after a complete timestamp.
+    TrailingCharacters,
+    /// A bracketed RFC 9557 annotation was malformed, out of place, or a critical offset
+    /// that disagreed with the timestamp.
+    InvalidAnnotation,
+    /// The resulting timestamp is outside of the supported range.
+    OutOfRange,
+}
+
+impl ParseErrorKind {
+    const fn as_str(self) -> &'static str {
+        match self {
+            ParseErrorKind::Empty => "empty input",
+            ParseErrorKind::UnexpectedEnd => "unexpected end of input",
+            ParseErrorKind::InvalidDigit => "invalid digit",
+            ParseErrorKind::UnexpectedCharacter => "unexpected character",
+            ParseErrorKind::MonthOutOfRange => "month out of range",
+            ParseErrorKind::InvalidDayForMonth => "invalid day for month",
+            ParseErrorKind::OrdinalOutOfRange => "day of year out of range",
+            ParseErrorKind::WeekOutOfRange => "week out of range",
+            ParseErrorKind::WeekdayOutOfRange => "weekday out of range",
+            ParseErrorKind::WeekdayMismatch => "weekday does not match date",
+            ParseErrorKind::HourOutOfRange => "hour out of range",
+            ParseErrorKind::MinuteOutOfRange => "minute out of range",
+            ParseErrorKind::SecondOutOfRange => "second out of range",
+            ParseErrorKind::InvalidFraction => "invalid fractional seconds",
+            ParseErrorKind::InvalidOffset => "invalid UTC offset",
+            ParseErrorKind::TrailingCharacters => "trailing characters",
+            ParseErrorKind::InvalidAnnotation => "invalid annotation",
+            ParseErrorKind::OutOfRange => "timestamp out of range",
+        }
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when a timestamp fails to parse, with the byte offset at which it failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    offset: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    #[cold]
+    #[inline(never)]
+    pub(crate) const fn new(offset: usize, kind: ParseErrorKind) -> Self {
+        ParseError { offset, kind }
+    }
+
+    /// Byte offset into the input where the error was detected.
+    #[inline(always)]
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The kind of error that occurred.
+    #[inline(always)]
+    #[must_use]
+    pub const fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid ISO8601 Timestamp: {} at byte {}",
+            self.kind, self.offset
+        )
+    }
+}
+
+impl core::error::Error for ParseError {}
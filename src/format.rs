@@ -1,21 +1,453 @@
 use generic_array::typenum as t;
 use time::{PrimitiveDateTime, UtcOffset};
 
-use crate::ts_str::{template, FormatString, IsValidFormat, TimestampStr};
+use crate::ts_str::{
+    http_date_template, offset_seconds_template, ordinal_template, template, trimmed_template, week_template,
+    DateFormatString, FormatString, HttpDateFormatString, IsValidFormat, OffsetSecondsFormatString,
+    OrdinalFormatString, SpecFormatString, TimestampStr, TrimFraction, TrimmedFormatString, VarStorage,
+    WeekFormatString,
+};
+use crate::FormatSpec;
+
+#[cfg(feature = "large-dates")]
+use crate::ts_str::{expanded_template, ExpandedFormatString};
+
+/// Date portion written by [`write_timestamp`]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DateLayout {
+    /// `YYYY-MM-DD`
+    Calendar,
+    /// `YYYY-DDD`
+    Ordinal,
+    /// `YYYY-Www-D`
+    Week,
+}
 
-#[rustfmt::skip]
-#[allow(unused_assignments, clippy::identity_op)]
 #[inline(always)]
-pub fn do_format<F: t::Bit, O: t::Bit, P: t::Unsigned>(ts: PrimitiveDateTime, offset: UtcOffset) -> TimestampStr<FormatString<F, O, P>>
+pub fn do_format<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+    ts: PrimitiveDateTime,
+    offset: UtcOffset,
+) -> TimestampStr<FormatString<F, O, P>>
 where
     FormatString<F, O, P>: IsValidFormat,
 {
-    // decompose timestamp
-    let (mut year, month, day) = crate::impls::to_calendar_date(ts.date());
-    let (hour, minute, second, nanoseconds) = ts.as_hms_nano();
+    let mut template = template::<F, O, P>();
+    write_timestamp::<F, O, P>(
+        template.as_mut(),
+        DateLayout::Calendar,
+        4,
+        ts,
+        offset,
+        false,
+        false,
+    );
+    TimestampStr(template)
+}
+
+/// Formats directly into the front of `buf`, returning the length written, or `None` if `buf` is
+/// shorter than the full template, which includes a `+` sign that is removed for positive years.
+#[inline(always)]
+pub fn do_format_into<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+    ts: PrimitiveDateTime,
+    offset: UtcOffset,
+    buf: &mut [u8],
+) -> Option<usize>
+where
+    FormatString<F, O, P>: IsValidFormat,
+{
+    let len = <<FormatString<F, O, P> as IsValidFormat>::Length as t::Unsigned>::USIZE;
+    let buf = buf.get_mut(..len)?;
+
+    buf.copy_from_slice(template::<F, O, P>().as_ref());
+
+    Some(do_format_in_place::<F, O, P>(ts, offset, buf))
+}
+
+/// Formats over a copy of the template already in `buf`, returning the length written.
+#[inline(always)]
+pub fn do_format_in_place<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+    ts: PrimitiveDateTime,
+    offset: UtcOffset,
+    buf: &mut [u8],
+) -> usize
+where
+    FormatString<F, O, P>: IsValidFormat,
+{
+    write_timestamp::<F, O, P>(buf, DateLayout::Calendar, 4, ts, offset, false, false);
+
+    // skip + sign if positive, as `TimestampStr` does
+    if buf[0] == b'+' {
+        buf.copy_within(1.., 0);
+        return buf.len() - 1;
+    }
+
+    buf.len()
+}
+
+#[inline(always)]
+pub fn do_format_offset_seconds<F: t::Bit, P: t::Unsigned>(
+    ts: PrimitiveDateTime,
+    offset: UtcOffset,
+) -> TimestampStr<OffsetSecondsFormatString<F, P>>
+where
+    FormatString<F, t::True, P>: IsValidFormat,
+    OffsetSecondsFormatString<F, P>:
+        IsValidFormat<Storage = VarStorage<<OffsetSecondsFormatString<F, P> as IsValidFormat>::Length>>,
+{
+    let mut template = offset_seconds_template::<F, P>();
+    let has_seconds = offset.seconds_past_minute() != 0;
+
+    write_timestamp::<F, t::True, P>(
+        template.as_mut(),
+        DateLayout::Calendar,
+        4,
+        ts,
+        offset,
+        has_seconds,
+        false,
+    );
+
+    if !has_seconds {
+        template.len -= 2 + F::BOOL as u8; // :ss
+    }
+
+    TimestampStr(template)
+}
+
+/// Formats with up to `P` fractional digits, dropping trailing zeros and moving the offset back over them
+#[inline(always)]
+pub fn do_format_trimmed<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+    ts: PrimitiveDateTime,
+    offset: UtcOffset,
+    trim: TrimFraction,
+) -> TimestampStr<TrimmedFormatString<F, O, P>>
+where
+    FormatString<F, O, P>: IsValidFormat,
+    TrimmedFormatString<F, O, P>:
+        IsValidFormat<Storage = VarStorage<<TrimmedFormatString<F, O, P> as IsValidFormat>::Length>>,
+{
+    let mut template = trimmed_template::<F, O, P>();
+    write_timestamp::<F, O, P>(
+        template.as_mut(),
+        DateLayout::Calendar,
+        4,
+        ts,
+        offset,
+        false,
+        false,
+    );
+
+    template.len = trim_fraction(
+        &mut template.buf,
+        template.len as usize,
+        suffix_len::<F, O>(),
+        P::USIZE,
+        trim,
+    ) as u8;
+
+    TimestampStr(template)
+}
+
+/// Length of the `Z`, `+0000` or `+00:00` at the end of a template.
+#[inline(always)]
+const fn suffix_len<F: t::Bit, O: t::Bit>() -> usize {
+    match (F::BOOL, O::BOOL) {
+        (_, false) => 1,
+        (false, true) => 5,
+        (true, true) => 6,
+    }
+}
+
+/// Drops trailing zeros from the `precision` fractional digits just before the `Z` or offset
+/// of `suffix` bytes, moving it back over them, and returns the new length.
+#[inline(always)]
+fn trim_fraction(buf: &mut [u8], len: usize, suffix: usize, precision: usize, trim: TrimFraction) -> usize {
+    if precision == 0 {
+        return len;
+    }
+
+    let end = len - suffix;
+    let start = end - precision;
+
+    let fraction = &buf[start..end];
+    let mut digits = precision - fraction.iter().rev().take_while(|&&c| c == b'0').count();
+
+    if let TrimFraction::Groups = trim {
+        digits = precision.min(digits.div_ceil(3) * 3);
+    }
 
+    // drop the decimal separator too when there are no digits left
+    let new_end = if digits == 0 { start - 1 } else { start + digits };
+
+    buf.copy_within(end..len, new_end);
+    len - (end - new_end)
+}
+
+/// Formats according to a [`FormatSpec`] chosen at runtime, by picking the typed format with the same
+/// layout and precision, then patching in the separators that only differ by a byte.
+#[inline(never)]
+pub fn do_format_spec(ts: PrimitiveDateTime, spec: &FormatSpec) -> TimestampStr<SpecFormatString> {
+    #[inline(always)]
+    fn typed<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+        ts: PrimitiveDateTime,
+        spec: &FormatSpec,
+    ) -> TimestampStr<SpecFormatString>
+    where
+        FormatString<F, O, P>: IsValidFormat,
+    {
+        let mut out: <SpecFormatString as IsValidFormat>::Storage = Default::default();
+
+        let mut len = <<FormatString<F, O, P> as IsValidFormat>::Length as t::Unsigned>::USIZE;
+        out.buf[..len].copy_from_slice(template::<F, O, P>().as_ref());
+
+        write_timestamp::<F, O, P>(
+            &mut out.buf[..len],
+            DateLayout::Calendar,
+            4,
+            ts,
+            UtcOffset::UTC,
+            false,
+            false,
+        );
+
+        // `+0000-00-00T00:00:00.` or `+00000000T000000.`
+        let (separator, decimal) = if F::BOOL { (11, 20) } else { (9, 16) };
+
+        out.buf[separator] = spec.separator;
+
+        if P::USIZE > 0 {
+            out.buf[decimal] = spec.decimal;
+        }
+
+        if let Some(trim) = spec.trim {
+            len = trim_fraction(&mut out.buf, len, suffix_len::<F, O>(), P::USIZE, trim);
+        }
+
+        out.len = len as u8;
+
+        TimestampStr(out)
+    }
+
+    macro_rules! dispatch {
+        ($($p:literal => $ty:ty),*) => {
+            match (spec.basic_format, spec.utc_offset, spec.precision) {
+                $(
+                    (false, false, $p) => typed::<t::True, t::False, $ty>(ts, spec),
+                    (false, true, $p) => typed::<t::True, t::True, $ty>(ts, spec),
+                    (true, false, $p) => typed::<t::False, t::False, $ty>(ts, spec),
+                    (true, true, $p) => typed::<t::False, t::True, $ty>(ts, spec),
+                )*
+                // SAFETY: precision is clamped to 9 by `FormatSpec::precision`
+                _ => unsafe { core::hint::unreachable_unchecked() },
+            }
+        };
+    }
+
+    dispatch!(0 => t::U0, 1 => t::U1, 2 => t::U2, 3 => t::U3, 4 => t::U4, 5 => t::U5, 6 => t::U6, 7 => t::U7, 8 => t::U8, 9 => t::U9)
+}
+
+/// Formats the last second of a minute as `:60` when it is a leap second
+#[inline(always)]
+pub fn do_format_leap_second<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+    ts: PrimitiveDateTime,
+    offset: UtcOffset,
+    leap_second: bool,
+) -> TimestampStr<FormatString<F, O, P>>
+where
+    FormatString<F, O, P>: IsValidFormat,
+{
     let mut template = template::<F, O, P>();
-    let buf = template.as_mut();
+    let leap_second = leap_second && ts.second() == 59;
+    write_timestamp::<F, O, P>(
+        template.as_mut(),
+        DateLayout::Calendar,
+        4,
+        ts,
+        offset,
+        false,
+        leap_second,
+    );
+    TimestampStr(template)
+}
+
+#[cfg(feature = "large-dates")]
+#[inline(always)]
+pub fn do_format_expanded<F: t::Bit, O: t::Bit, P: t::Unsigned, Y: t::Unsigned>(
+    ts: PrimitiveDateTime,
+    offset: UtcOffset,
+) -> TimestampStr<ExpandedFormatString<F, O, P, Y>>
+where
+    FormatString<F, O, P>: IsValidFormat,
+    ExpandedFormatString<F, O, P, Y>: IsValidFormat,
+{
+    let mut template = expanded_template::<F, O, P, Y>();
+    write_timestamp::<F, O, P>(
+        template.as_mut(),
+        DateLayout::Calendar,
+        4 + Y::USIZE,
+        ts,
+        offset,
+        false,
+        false,
+    );
+    TimestampStr(template)
+}
+
+/// Formats only the date, by truncating the calendar format without fractional seconds
+#[inline(always)]
+pub fn do_format_date<F: t::Bit>(ts: PrimitiveDateTime) -> TimestampStr<DateFormatString<F>>
+where
+    FormatString<F, t::False, t::U0>: IsValidFormat,
+    DateFormatString<F>: IsValidFormat,
+{
+    let full = do_format::<F, t::False, t::U0>(ts, UtcOffset::UTC);
+
+    let mut date: <DateFormatString<F> as IsValidFormat>::Storage = Default::default();
+    let buf = date.as_mut();
+    buf.copy_from_slice(&full.0.as_ref()[..buf.len()]);
+
+    TimestampStr(date)
+}
+
+#[inline(always)]
+pub fn do_format_ordinal<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+    ts: PrimitiveDateTime,
+    offset: UtcOffset,
+) -> TimestampStr<OrdinalFormatString<F, O, P>>
+where
+    OrdinalFormatString<F, O, P>: IsValidFormat,
+{
+    let mut template = ordinal_template::<F, O, P>();
+    write_timestamp::<F, O, P>(
+        template.as_mut(),
+        DateLayout::Ordinal,
+        4,
+        ts,
+        offset,
+        false,
+        false,
+    );
+    TimestampStr(template)
+}
+
+#[inline(always)]
+pub fn do_format_week<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+    ts: PrimitiveDateTime,
+    offset: UtcOffset,
+) -> TimestampStr<WeekFormatString<F, O, P>>
+where
+    WeekFormatString<F, O, P>: IsValidFormat,
+{
+    let mut template = week_template::<F, O, P>();
+    write_timestamp::<F, O, P>(template.as_mut(), DateLayout::Week, 4, ts, offset, false, false);
+    TimestampStr(template)
+}
+
+/// Formats as an IMF-fixdate, `Sun, 17 Oct 2021 02:03:01 GMT`, which is always in UTC
+/// and has no room for fractional seconds or years outside of 0000-9999.
+#[inline(always)]
+pub fn do_format_http_date(ts: PrimitiveDateTime) -> TimestampStr<HttpDateFormatString> {
+    const WEEKDAYS: [&[u8; 3]; 7] = [b"Mon", b"Tue", b"Wed", b"Thu", b"Fri", b"Sat", b"Sun"];
+    #[rustfmt::skip]
+    const MONTHS: [&[u8; 3]; 12] = [
+        b"Jan", b"Feb", b"Mar", b"Apr", b"May", b"Jun", b"Jul", b"Aug", b"Sep", b"Oct", b"Nov", b"Dec",
+    ];
+
+    let mut template = http_date_template();
+    let buf = template.as_mut_slice();
+
+    // NOTE: years outside of 0000-9999 saturate, as there is no room for a sign or more digits
+    let ts = match ts.year() {
+        year if year < 0 => time::macros::datetime!(0000-01-01 00:00),
+        year if year > 9999 => time::macros::datetime!(9999-12-31 23:59:59),
+        _ => ts,
+    };
+
+    let (year, month, day) = crate::impls::to_calendar_date(ts.date());
+    let (hour, minute, second) = ts.as_hms();
+    let year = year as u16;
+
+    let mut write_2 = |pos: usize, value: u8| {
+        buf[pos] = b'0' + value / 10;
+        buf[pos + 1] = b'0' + value % 10;
+    };
+
+    write_2(5, day);
+    write_2(12, (year / 100) as u8);
+    write_2(14, (year % 100) as u8);
+    write_2(17, hour);
+    write_2(20, minute);
+    write_2(23, second);
+
+    buf[0..3].copy_from_slice(WEEKDAYS[ts.weekday().number_days_from_monday() as usize]);
+    buf[8..11].copy_from_slice(MONTHS[month as usize - 1]);
+
+    TimestampStr(template)
+}
+
+/// Clamps to the first or last timestamp whose year, in the given layout, fits within `digits` digits
+#[cfg(feature = "large-dates")]
+#[cold]
+fn saturate_year(ts: PrimitiveDateTime, layout: DateLayout, digits: usize) -> PrimitiveDateTime {
+    use time::{util::weeks_in_year, Date, Month, Time, Weekday};
+
+    let max = 10i32.pow(digits as u32) - 1;
+
+    let (first, last) = match layout {
+        DateLayout::Week => (
+            Date::from_iso_week_date(-max, 1, Weekday::Monday),
+            Date::from_iso_week_date(max, weeks_in_year(max), Weekday::Sunday),
+        ),
+        _ => (
+            Date::from_calendar_date(-max, Month::January, 1),
+            Date::from_calendar_date(max, Month::December, 31),
+        ),
+    };
+
+    match (first, last) {
+        (Ok(first), _) if ts.date() < first => first.midnight(),
+        (_, Ok(last)) if ts.date() > last => last.with_time(Time::MAX),
+        _ => ts,
+    }
+}
+
+#[rustfmt::skip]
+#[allow(unused_assignments, clippy::identity_op)]
+#[cfg_attr(not(feature = "large-dates"), allow(unused_variables))]
+#[inline(always)]
+fn write_timestamp<F: t::Bit, O: t::Bit, P: t::Unsigned>(buf: &mut [u8], layout: DateLayout, year_digits: usize, ts: PrimitiveDateTime, offset: UtcOffset, offset_seconds: bool, leap_second: bool) {
+    // decompose timestamp
+    let decompose = |ts: PrimitiveDateTime| match layout {
+        DateLayout::Calendar => {
+            let (year, month, day) = crate::impls::to_calendar_date(ts.date());
+            (year, month as u16, day)
+        }
+        DateLayout::Ordinal => {
+            let (year, ordinal) = ts.to_ordinal_date();
+            (year, ordinal, 0)
+        }
+        DateLayout::Week => {
+            // NOTE: this is the ISO week-numbering year, which may differ from the calendar year
+            let (year, week, weekday) = ts.to_iso_week_date();
+            (year, week as u16, weekday.number_from_monday())
+        }
+    };
+
+    #[cfg(not(feature = "large-dates"))]
+    let (mut year, month, day) = decompose(ts);
+
+    // NOTE: `large-dates` allows years beyond what the given digits can hold, so those saturate
+    // to the nearest timestamp that fits, rather than being written modulo 10^digits
+    #[cfg(feature = "large-dates")]
+    let (ts, (mut year, month, day)) = match decompose(ts) {
+        (year, ..) if unlikely!(year.unsigned_abs() >= 10u32.pow(year_digits as u32)) => {
+            let ts = saturate_year(ts, layout, year_digits);
+            (ts, decompose(ts))
+        }
+        parts => (ts, parts),
+    };
+
+    let (hour, minute, second, nanoseconds) = ts.as_hms_nano();
 
     if unlikely!(year < 0) {
         year = -year; // formatting only accepts unsigned integers
@@ -55,13 +487,35 @@ where
         }};
     }
 
+    #[cfg(feature = "large-dates")]
+    match year_digits {
+        4 => write_num!(year as u16,    4, 9999),       // YYYY-
+        5 => write_num!(year as u32,    5, 99999),      // YYYYY-
+        _ => write_num!(year as u32,    6, 999999),     // YYYYYY-
+    }
+
+    #[cfg(not(feature = "large-dates"))]
     write_num!(year as u16,     4, 9999);       // YYYY-
-    write_num!(month as u8,     2, 12);         // MM-
-    write_num!(day,             2, 31);         // DDT?
+
+    match layout {
+        DateLayout::Calendar => {
+            write_num!(month as u8, 2, 12);     // MM-
+            write_num!(day,         2, 31);     // DDT?
+        }
+        DateLayout::Ordinal => {
+            write_num!(month,       3, 366);    // DDDT?
+        }
+        DateLayout::Week => {
+            pos += 1;                           // W
+            write_num!(month as u8, 2, 53);     // ww-
+            write_num!(day,         1, 7);      // DT?
+        }
+    }
+
     if !F::BOOL { pos += 1; }                   // T
     write_num!(hour,            2, 59);         // HH:
     write_num!(minute,          2, 59);         // mm:
-    write_num!(second,          2, 59);         // ss.?(if full)
+    write_num!(second + leap_second as u8, 2, 60); // ss.?(if full)
     // if not full format and has subseconds, accept period.
     if !F::BOOL && P::USIZE > 0 { pos += 1; }   // .
 
@@ -87,14 +541,15 @@ where
             buf[pos - 1] = b'-';
         }
 
-        let (h, m, _) = offset.as_hms();
+        let (h, m, s) = offset.as_hms();
 
         write_num!(h.abs(), 2, 23); // HZ
-        if !F::BOOL { pos += 1; }   // :
         write_num!(m.abs(), 2, 59); // MZ
-    }
 
-    TimestampStr(template)
+        if offset_seconds {
+            write_num!(s.abs(), 2, 59); // SZ
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,325 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+use generic_array::typenum as t;
+use time::UtcOffset;
+
+use crate::formats;
+use crate::ts_str::{FormatString, IsValidFormat, TimestampStr};
+use crate::Timestamp;
+
+/// A [`Timestamp`] that is displayed, serialized and described by schemas using the format `S`,
+/// rather than always with millisecond precision.
+///
+/// When serialized to binary formats, the timestamp is written as an `i64` number of whole seconds,
+/// milliseconds, microseconds or nanoseconds since the Unix Epoch, whichever is the coarsest unit
+/// that keeps the precision of `S`. Integers are read back in that same unit.
+///
+/// With `large-dates`, years beyond ±9999 are written in full as expanded years with nanosecond precision,
+/// rather than saturating as `S` would.
+///
+/// ```rust
+/// # use iso8601_timestamp::{Timestamp, TimestampMicros};
+/// let ts = TimestampMicros::new(Timestamp::parse("2021-10-17T02:03:01.123456789Z").unwrap());
+///
+/// assert_eq!(ts.to_string(), "2021-10-17T02:03:01.123456Z");
+/// assert_eq!(serde_json::to_string(&ts).unwrap(), r#""2021-10-17T02:03:01.123456Z""#);
+/// ```
+#[repr(transparent)]
+pub struct Formatted<S = formats::FullMilliseconds>(pub Timestamp, PhantomData<S>);
+
+/// `2023-03-24T07:05:59Z`
+pub type TimestampSeconds = Formatted<formats::FullSeconds>;
+/// `2023-03-24T07:05:59.005Z`
+pub type TimestampMillis = Formatted<formats::FullMilliseconds>;
+/// `2023-03-24T07:05:59.005000Z`, as stored by PostgreSQL
+pub type TimestampMicros = Formatted<formats::FullMicroseconds>;
+/// `2023-03-24T07:05:59.005432101Z`
+pub type TimestampNanos = Formatted<formats::FullNanoseconds>;
+
+impl<S> Formatted<S> {
+    /// Wraps a [`Timestamp`] to be formatted as `S`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(ts: Timestamp) -> Self {
+        Formatted(ts, PhantomData)
+    }
+
+    /// Returns the wrapped [`Timestamp`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn into_inner(self) -> Timestamp {
+        self.0
+    }
+}
+
+impl<F: t::Bit, O: t::Bit, P: t::Unsigned> Formatted<FormatString<F, O, P>>
+where
+    FormatString<F, O, P>: IsValidFormat,
+{
+    /// Formats the timestamp as `FormatString<F, O, P>`, in UTC.
+    #[inline(always)]
+    #[must_use]
+    pub fn format(&self) -> TimestampStr<FormatString<F, O, P>> {
+        self.0.format_raw(UtcOffset::UTC)
+    }
+}
+
+impl<S> From<Timestamp> for Formatted<S> {
+    #[inline(always)]
+    fn from(ts: Timestamp) -> Self {
+        Formatted::new(ts)
+    }
+}
+
+impl<S> From<Formatted<S>> for Timestamp {
+    #[inline(always)]
+    fn from(ts: Formatted<S>) -> Self {
+        ts.0
+    }
+}
+
+impl<S> Deref for Formatted<S> {
+    type Target = Timestamp;
+
+    #[inline(always)]
+    fn deref(&self) -> &Timestamp {
+        &self.0
+    }
+}
+
+impl<S> DerefMut for Formatted<S> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Timestamp {
+        &mut self.0
+    }
+}
+
+// implemented by hand, as derives would require the same of `S`
+
+impl<S> Clone for Formatted<S> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Formatted<S> {}
+
+impl<S> PartialEq for Formatted<S> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<S> Eq for Formatted<S> {}
+
+impl<S> PartialOrd for Formatted<S> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Formatted<S> {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<S> Hash for Formatted<S> {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<F: t::Bit, O: t::Bit, P: t::Unsigned> fmt::Debug for Formatted<FormatString<F, O, P>>
+where
+    FormatString<F, O, P>: IsValidFormat,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.format_expanded_fallback() {
+            Some(ts) => f.debug_tuple("Formatted").field(&ts).finish(),
+            None => f.debug_tuple("Formatted").field(&self.format()).finish(),
+        }
+    }
+}
+
+impl<F: t::Bit, O: t::Bit, P: t::Unsigned> fmt::Display for Formatted<FormatString<F, O, P>>
+where
+    FormatString<F, O, P>: IsValidFormat,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.format_expanded_fallback() {
+            Some(ts) => fmt::Display::fmt(&ts, f),
+            None => fmt::Display::fmt(&self.format(), f),
+        }
+    }
+}
+
+/// Number of binary units per second for `P` fractional digits, rounded up to whole
+/// milliseconds, microseconds or nanoseconds.
+#[cfg(feature = "serde")]
+const fn units_per_second<P: t::Unsigned>() -> i64 {
+    match P::USIZE {
+        0 => 1,
+        1..=3 => 1_000,
+        4..=6 => 1_000_000,
+        _ => 1_000_000_000,
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use core::convert::TryFrom;
+    use core::fmt;
+    use core::marker::PhantomData;
+
+    use generic_array::typenum as t;
+    use serde_core::de::{self, Deserialize, Deserializer, Visitor};
+    use serde_core::ser::{self, Serialize, Serializer};
+
+    use super::{units_per_second, Formatted};
+    use crate::serde::{from_units, to_units};
+    use crate::ts_str::{FormatString, IsValidFormat};
+    use crate::Timestamp;
+
+    const OUT_OF_RANGE: &str = "Timestamp out of range";
+
+    impl<F: t::Bit, O: t::Bit, P: t::Unsigned> Serialize for Formatted<FormatString<F, O, P>>
+    where
+        FormatString<F, O, P>: IsValidFormat,
+    {
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if serializer.is_human_readable() {
+                return match self.0.format_expanded_fallback() {
+                    Some(ts) => serializer.serialize_str(&ts),
+                    None => self.format().serialize(serializer),
+                };
+            }
+
+            match to_units(&self.0, units_per_second::<P>()) {
+                Some(units) => units.serialize(serializer),
+                None => Err(ser::Error::custom(OUT_OF_RANGE)),
+            }
+        }
+    }
+
+    impl<'de, F: t::Bit, O: t::Bit, P: t::Unsigned> Deserialize<'de> for Formatted<FormatString<F, O, P>>
+    where
+        FormatString<F, O, P>: IsValidFormat,
+    {
+        #[inline]
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct FormattedVisitor<P>(PhantomData<P>);
+
+            impl<P: t::Unsigned> Visitor<'_> for FormattedVisitor<P> {
+                type Value = Timestamp;
+
+                #[inline]
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("an ISO8601 Timestamp")
+                }
+
+                #[inline]
+                fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where
+                    E: de::Error,
+                {
+                    Timestamp::try_parse_or_expanded(v).map_err(E::custom)
+                }
+
+                #[inline]
+                fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+                where
+                    E: de::Error,
+                {
+                    from_units(v, units_per_second::<P>()).ok_or_else(|| E::custom(OUT_OF_RANGE))
+                }
+
+                #[inline]
+                fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+                where
+                    E: de::Error,
+                {
+                    match i64::try_from(v) {
+                        Ok(v) => self.visit_i64(v),
+                        Err(_) => Err(E::custom(OUT_OF_RANGE)),
+                    }
+                }
+            }
+
+            deserializer
+                .deserialize_any(FormattedVisitor::<P>(PhantomData))
+                .map(Formatted::new)
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+mod schema_impl {
+    use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+    extern crate alloc;
+
+    use alloc::borrow::Cow;
+    use alloc::format;
+
+    use generic_array::typenum as t;
+
+    use super::Formatted;
+    use crate::ts_str::{FormatString, IsValidFormat};
+    use crate::Timestamp;
+
+    impl<F: t::Bit, O: t::Bit, P: t::Unsigned> JsonSchema for Formatted<FormatString<F, O, P>>
+    where
+        FormatString<F, O, P>: IsValidFormat,
+    {
+        fn schema_name() -> Cow<'static, str> {
+            Cow::Owned(format!("ISO8601 Timestamp with {} fractional digits", P::USIZE))
+        }
+
+        fn schema_id() -> Cow<'static, str> {
+            Cow::Owned(format!(
+                "iso8601_timestamp::Formatted<{}, {}, {}>",
+                F::BOOL,
+                O::BOOL,
+                P::USIZE
+            ))
+        }
+
+        fn json_schema(_: &mut SchemaGenerator) -> Schema {
+            let example = Formatted::<FormatString<F, O, P>>::new(Timestamp::UNIX_EPOCH).format();
+
+            // only the full format is an RFC 3339 `date-time`
+            match F::BOOL {
+                true => json_schema!({
+                    "type": "string",
+                    "format": "date-time",
+                    "description": "ISO8601 formatted timestamp",
+                    "examples": [&*example],
+                }),
+                false => json_schema!({
+                    "type": "string",
+                    "description": "ISO8601 formatted timestamp without punctuation",
+                    "examples": [&*example],
+                }),
+            }
+        }
+    }
+}
@@ -0,0 +1,525 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::duration::{parse_duration, write_duration, write_u64};
+use crate::error::{ParseError, ParseErrorKind as K};
+use crate::parse::{parse_iso8601, parse_iso8601_or_expanded};
+use crate::ts_str::{IntervalFormatString, IsValidFormat, RepeatingIntervalFormatString, TimestampStr};
+use crate::{IsoDuration, Timestamp};
+
+/// An ISO8601 time interval, given by any two of its start, end and duration.
+///
+/// ```rust
+/// # use iso8601_timestamp::{Interval, Timestamp};
+/// let interval = Interval::parse("2021-10-17T09:00Z/PT8H").unwrap();
+///
+/// assert_eq!(interval.end(), Timestamp::parse("2021-10-17T17:00:00Z"));
+/// assert_eq!(interval.format(), "2021-10-17T09:00:00.000Z/PT8H");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interval {
+    /// `start/end`
+    StartEnd(Timestamp, Timestamp),
+    /// `start/duration`
+    StartDuration(Timestamp, IsoDuration),
+    /// `duration/end`
+    DurationEnd(IsoDuration, Timestamp),
+}
+
+impl Interval {
+    /// The start of the interval, or `None` if it would be out of range.
+    #[must_use]
+    pub fn start(&self) -> Option<Timestamp> {
+        match *self {
+            Interval::StartEnd(start, _) | Interval::StartDuration(start, _) => Some(start),
+            Interval::DurationEnd(duration, end) => (-duration).checked_add_to(*end).map(Timestamp),
+        }
+    }
+
+    /// The end of the interval, or `None` if it would be out of range.
+    #[must_use]
+    pub fn end(&self) -> Option<Timestamp> {
+        match *self {
+            Interval::StartEnd(_, end) | Interval::DurationEnd(_, end) => Some(end),
+            Interval::StartDuration(start, duration) => duration.checked_add_to(*start).map(Timestamp),
+        }
+    }
+
+    /// Parse an interval in any of the forms `start/end`, `start/duration` or `duration/end`.
+    ///
+    /// The end may omit any leading components that are the same as the start,
+    /// such as `2021-10-17T09:00/17:00` or `2021-10-17/10-19`, keeping the offset of the start.
+    #[inline(never)]
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        parse_interval(s.as_bytes()).ok()
+    }
+
+    /// Parse an interval, like [`Interval::parse`], but returns a [`ParseError`]
+    /// describing why and where parsing failed.
+    #[inline(never)]
+    pub fn try_parse(s: &str) -> Result<Self, ParseError> {
+        parse_interval(s.as_bytes())
+    }
+
+    /// Format the interval without allocating, keeping the form it was given in.
+    #[must_use]
+    pub fn format(&self) -> TimestampStr<IntervalFormatString> {
+        let mut value: <IntervalFormatString as IsValidFormat>::Storage = Default::default();
+
+        let len = write_interval(&mut value.buf, self);
+        value.len = len as u8;
+
+        TimestampStr(value)
+    }
+
+    /// The start and end of the `n`th repetition, where repetitions follow each other for
+    /// `start/end` and `start/duration`, and precede each other for `duration/end`.
+    fn nth(&self, n: i64) -> Option<Range<Timestamp>> {
+        let range = match *self {
+            Interval::StartEnd(start, end) => {
+                let step = end.0 - start.0;
+                let start = start.checked_add(step.checked_mul(i32::try_from(n).ok()?)?)?;
+                start..start.checked_add(step)?
+            }
+            Interval::StartDuration(start, duration) => {
+                let next = Timestamp(duration.checked_add_times_to(*start, n + 1)?);
+                Timestamp(duration.checked_add_times_to(*start, n)?)..next
+            }
+            Interval::DurationEnd(duration, end) => {
+                let prev = Timestamp(duration.checked_add_times_to(*end, -(n + 1))?);
+                prev..Timestamp(duration.checked_add_times_to(*end, -n)?)
+            }
+        };
+
+        Some(range)
+    }
+}
+
+/// An ISO8601 repeating interval, such as `R5/2021-10-17T00:00:00Z/PT1H`,
+/// or `R/2021-10-17T00:00:00Z/PT1H` to repeat without end.
+///
+/// ```rust
+/// # use iso8601_timestamp::{RepeatingInterval, Timestamp};
+/// let repeating = RepeatingInterval::parse("R3/2021-01-31T00:00:00Z/P1M").unwrap();
+///
+/// let starts: Vec<_> = repeating.occurrences().map(|occurrence| occurrence.start).collect();
+///
+/// assert_eq!(starts, [
+///     Timestamp::parse("2021-01-31T00:00:00Z").unwrap(),
+///     Timestamp::parse("2021-02-28T00:00:00Z").unwrap(),
+///     Timestamp::parse("2021-03-31T00:00:00Z").unwrap(),
+/// ]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RepeatingInterval {
+    /// Number of occurrences, or `None` to repeat without end
+    pub repetitions: Option<u32>,
+    /// The first occurrence, or the last for the `duration/end` form
+    pub interval: Interval,
+}
+
+impl RepeatingInterval {
+    /// Parse a repeating interval, `Rn/interval` or `R/interval`, where the interval
+    /// is in any of the forms accepted by [`Interval::parse`].
+    #[inline(never)]
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        parse_repeating(s.as_bytes()).ok()
+    }
+
+    /// Parse a repeating interval, like [`RepeatingInterval::parse`], but returns a [`ParseError`]
+    /// describing why and where parsing failed.
+    #[inline(never)]
+    pub fn try_parse(s: &str) -> Result<Self, ParseError> {
+        parse_repeating(s.as_bytes())
+    }
+
+    /// Format the repeating interval without allocating.
+    #[must_use]
+    pub fn format(&self) -> TimestampStr<RepeatingIntervalFormatString> {
+        let mut value: <RepeatingIntervalFormatString as IsValidFormat>::Storage = Default::default();
+
+        let buf = &mut value.buf;
+        buf[0] = b'R';
+        let mut pos = 1;
+
+        if let Some(repetitions) = self.repetitions {
+            pos += write_u64(&mut buf[pos..], repetitions as u64);
+        }
+
+        buf[pos] = b'/';
+        pos += 1;
+        pos += write_interval(&mut buf[pos..], &self.interval);
+        value.len = pos as u8;
+
+        TimestampStr(value)
+    }
+
+    /// Iterates over the start and end of each occurrence, stopping early if one would be out of range.
+    ///
+    /// Occurrences of the `duration/end` form run backwards from the end, as the end is that of the last.
+    /// Nominal durations are scaled before being added, so monthly occurrences from the 31st
+    /// fall on the last day of shorter months without drifting.
+    #[inline]
+    #[must_use]
+    pub fn occurrences(&self) -> Occurrences {
+        Occurrences {
+            interval: self.interval,
+            next: 0,
+            remaining: self.repetitions,
+        }
+    }
+}
+
+impl IntoIterator for RepeatingInterval {
+    type Item = Range<Timestamp>;
+    type IntoIter = Occurrences;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.occurrences()
+    }
+}
+
+/// Iterator over the occurrences of a [`RepeatingInterval`], as ranges of timestamps.
+#[derive(Debug, Clone)]
+pub struct Occurrences {
+    interval: Interval,
+    next: i64,
+    remaining: Option<u32>,
+}
+
+impl Iterator for Occurrences {
+    type Item = Range<Timestamp>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+
+        let Some(range) = self.interval.nth(self.next) else {
+            self.remaining = Some(0);
+            return None;
+        };
+
+        self.next += 1;
+        if let Some(ref mut remaining) = self.remaining {
+            *remaining -= 1;
+        }
+
+        Some(range)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(remaining) => (0, Some(remaining as usize)),
+            None => (0, None),
+        }
+    }
+}
+
+impl FusedIterator for Occurrences {}
+
+fn write_interval(buf: &mut [u8], interval: &Interval) -> usize {
+    fn write_ts(buf: &mut [u8], ts: Timestamp) -> usize {
+        fn write_str(buf: &mut [u8], s: &str) -> usize {
+            buf[..s.len()].copy_from_slice(s.as_bytes());
+            s.len()
+        }
+
+        // years beyond ±9999 are written in full, as with `Timestamp`'s `Display`
+        match ts.format_expanded_fallback() {
+            Some(expanded) => write_str(buf, &expanded),
+            None => write_str(buf, &ts.format()),
+        }
+    }
+
+    let mut pos = match *interval {
+        Interval::StartEnd(start, _) | Interval::StartDuration(start, _) => write_ts(buf, start),
+        Interval::DurationEnd(duration, _) => write_duration(buf, &duration),
+    };
+
+    buf[pos] = b'/';
+    pos += 1;
+
+    pos + match *interval {
+        Interval::StartEnd(_, end) | Interval::DurationEnd(_, end) => write_ts(&mut buf[pos..], end),
+        Interval::StartDuration(_, duration) => write_duration(&mut buf[pos..], &duration),
+    }
+}
+
+/// Either half of an interval
+enum Part {
+    Timestamp(Timestamp),
+    Duration(IsoDuration),
+}
+
+/// Parses one half of an interval starting at `offset`, keeping error offsets relative to the whole input.
+fn parse_part(b: &[u8], offset: usize) -> Result<Part, ParseError> {
+    let shift = |e: ParseError| ParseError::new(offset + e.offset(), e.kind());
+
+    match b.first() {
+        Some(b'P' | b'p') => parse_duration(b).map(Part::Duration).map_err(shift),
+        _ => parse_iso8601_or_expanded(b)
+            .map(|ts| Part::Timestamp(Timestamp(ts)))
+            .map_err(shift),
+    }
+}
+
+fn parse_interval(b: &[u8]) -> Result<Interval, ParseError> {
+    if b.is_empty() {
+        return Err(ParseError::new(0, K::Empty));
+    }
+
+    let Some(split) = b.iter().position(|&c| c == b'/') else {
+        return Err(ParseError::new(b.len(), K::UnexpectedEnd));
+    };
+
+    let (first, second) = (&b[..split], &b[split + 1..]);
+
+    if second.is_empty() {
+        return Err(ParseError::new(b.len(), K::UnexpectedEnd));
+    }
+
+    if let Some(extra) = second.iter().position(|&c| c == b'/') {
+        return Err(ParseError::new(split + 1 + extra, K::TrailingCharacters));
+    }
+
+    Ok(match parse_part(first, 0)? {
+        Part::Timestamp(start) => match parse_part(second, split + 1) {
+            Ok(Part::Timestamp(end)) => Interval::StartEnd(start, end),
+            Ok(Part::Duration(duration)) => Interval::StartDuration(start, duration),
+            Err(e) => match abbreviated_end(first, second) {
+                Some(end) => Interval::StartEnd(start, end),
+                None => return Err(e),
+            },
+        },
+        Part::Duration(duration) => match parse_part(second, split + 1)? {
+            Part::Timestamp(end) => Interval::DurationEnd(duration, end),
+            Part::Duration(_) => return Err(ParseError::new(split + 1, K::UnexpectedCharacter)),
+        },
+    })
+}
+
+/// Completes an end such as `17:00` or `10-19T17:00` from the start it follows, replacing
+/// the trailing components of the start's date and taking its offset if the end has none.
+fn abbreviated_end(start: &[u8], end: &[u8]) -> Option<Timestamp> {
+    fn split(b: &[u8]) -> (&[u8], &[u8]) {
+        match b.iter().position(|&c| matches!(c, b'T' | b't' | b' ')) {
+            Some(i) => (&b[..i], &b[i..]),
+            // a bare time, such as `17:00`
+            None if b.contains(&b':') => (&[], b),
+            None => (b, &[]),
+        }
+    }
+
+    fn offset(time: &[u8]) -> &[u8] {
+        match time.iter().position(|&c| matches!(c, b'Z' | b'z' | b'+' | b'-')) {
+            Some(i) => &time[i..],
+            None => &[],
+        }
+    }
+
+    let (start_date, start_time) = split(start);
+    let (end_date, end_time) = split(end);
+
+    // components replaced in the date must have the same shape, such as `10-19` for `10-17`
+    let keep = start_date.len().checked_sub(end_date.len())?;
+    let same_shape = start_date[keep..]
+        .iter()
+        .zip(end_date)
+        .all(|(a, b)| a.is_ascii_digit() == b.is_ascii_digit());
+
+    if !same_shape {
+        return None;
+    }
+
+    let mut buf = [0u8; 64];
+    let mut len = 0;
+
+    let mut push = |part: &[u8]| -> Option<()> {
+        buf.get_mut(len..len + part.len())?.copy_from_slice(part);
+        len += part.len();
+        Some(())
+    };
+
+    push(&start_date[..keep])?;
+    push(end_date)?;
+
+    if !end_time.is_empty() {
+        if end_date.is_empty() && end_time[0] != b'T' {
+            push(start_time.get(..1).unwrap_or(b"T"))?;
+        }
+
+        push(end_time)?;
+
+        if offset(end_time).is_empty() {
+            push(offset(start_time))?;
+        }
+    }
+
+    parse_iso8601(&buf[..len]).ok().map(Timestamp)
+}
+
+fn parse_repeating(b: &[u8]) -> Result<RepeatingInterval, ParseError> {
+    match b.first() {
+        None => return Err(ParseError::new(0, K::Empty)),
+        Some(b'R' | b'r') => {}
+        Some(_) => return Err(ParseError::new(0, K::UnexpectedCharacter)),
+    }
+
+    let mut pos = 1;
+    let mut repetitions: Option<u32> = None;
+
+    while let Some(c @ b'0'..=b'9') = b.get(pos) {
+        let value = repetitions.unwrap_or(0) as u64 * 10 + (c - b'0') as u64;
+
+        if value > u32::MAX as u64 {
+            return Err(ParseError::new(1, K::OutOfRange));
+        }
+
+        repetitions = Some(value as u32);
+        pos += 1;
+    }
+
+    match b.get(pos) {
+        Some(b'/') => pos += 1,
+        Some(_) => return Err(ParseError::new(pos, K::UnexpectedCharacter)),
+        None => return Err(ParseError::new(pos, K::UnexpectedEnd)),
+    }
+
+    match parse_interval(&b[pos..]) {
+        Ok(interval) => Ok(RepeatingInterval {
+            repetitions,
+            interval,
+        }),
+        Err(e) if e.kind() == K::Empty => Err(ParseError::new(b.len(), K::UnexpectedEnd)),
+        Err(e) => Err(ParseError::new(pos + e.offset(), e.kind())),
+    }
+}
+
+impl core::str::FromStr for Interval {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Interval::try_parse(s)
+    }
+}
+
+impl core::str::FromStr for RepeatingInterval {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RepeatingInterval::try_parse(s)
+    }
+}
+
+impl fmt::Display for Interval {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format())
+    }
+}
+
+impl fmt::Display for RepeatingInterval {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format())
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde_core::de::{Deserialize, Deserializer, Error, Visitor};
+    use serde_core::ser::{Serialize, Serializer};
+
+    use super::{Interval, RepeatingInterval};
+
+    impl Serialize for Interval {
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.format().serialize(serializer)
+        }
+    }
+
+    impl Serialize for RepeatingInterval {
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.format().serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Interval {
+        #[inline]
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            use core::fmt;
+
+            struct IntervalVisitor;
+
+            impl Visitor<'_> for IntervalVisitor {
+                type Value = Interval;
+
+                #[inline]
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("an ISO8601 time interval")
+                }
+
+                #[inline]
+                fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where
+                    E: Error,
+                {
+                    Interval::try_parse(v).map_err(E::custom)
+                }
+            }
+
+            deserializer.deserialize_str(IntervalVisitor)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for RepeatingInterval {
+        #[inline]
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            use core::fmt;
+
+            struct RepeatingIntervalVisitor;
+
+            impl Visitor<'_> for RepeatingIntervalVisitor {
+                type Value = RepeatingInterval;
+
+                #[inline]
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("an ISO8601 repeating interval")
+                }
+
+                #[inline]
+                fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                where
+                    E: Error,
+                {
+                    RepeatingInterval::try_parse(v).map_err(E::custom)
+                }
+            }
+
+            deserializer.deserialize_str(RepeatingIntervalVisitor)
+        }
+    }
+}
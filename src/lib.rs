@@ -26,13 +26,38 @@ use typenum as t;
 #[macro_use]
 mod macros;
 
+mod annotation;
+mod duration;
+mod error;
 mod format;
+mod formatted;
 mod impls;
+mod interval;
+mod options;
 mod parse;
+mod parsed;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod spec;
 mod ts_str;
 
+pub use annotation::{Annotated, Annotation, AnnotationIter, Annotations};
+pub use duration::IsoDuration;
+pub use error::{ParseError, ParseErrorKind};
+pub use formatted::{Formatted, TimestampMicros, TimestampMillis, TimestampNanos, TimestampSeconds};
+pub use interval::{Interval, Occurrences, RepeatingInterval};
+pub use options::{ParseOptions, TimezoneRule};
+pub use parsed::{ParsedOffset, Precision};
+pub use spec::FormatSpec;
 use ts_str::IsValidFormat;
-pub use ts_str::{FormatString, TimestampStr};
+pub use ts_str::{
+    DateFormatString, DurationFormatString, FormatString, HttpDateFormatString, IntervalFormatString,
+    OffsetSecondsFormatString, OrdinalFormatString, RepeatingIntervalFormatString, SpecFormatString,
+    TimestampStr, TrimFraction, TrimmedFormatString, WeekFormatString,
+};
+
+#[cfg(feature = "large-dates")]
+pub use ts_str::ExpandedFormatString;
 
 /// UTC Timestamp with nanosecond precision, millisecond-precision when serialized to serde (JSON).
 ///
@@ -49,16 +74,77 @@ use core::fmt;
 impl fmt::Debug for Timestamp {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ts) = self.format_expanded_fallback() {
+            return f.debug_tuple("Timestamp").field(&ts).finish();
+        }
+
         f.debug_tuple("Timestamp")
             .field(&self.format_nanoseconds())
             .finish()
     }
 }
 
+/// Formats as with [`Timestamp::format`] by default, while the formatter's precision picks the number
+/// of fractional digits, up to 9, and the alternate flag picks the basic layout without punctuation.
+/// Width, fill and alignment are also honoured.
+///
+/// With `large-dates`, years beyond ±9999 are written as expanded years with nanosecond precision instead,
+/// regardless of the precision and alternate flag.
+///
+/// ```rust
+/// # use iso8601_timestamp::Timestamp;
+/// let ts = Timestamp::parse("2021-10-17T02:03:01.123456Z").unwrap();
+///
+/// assert_eq!(format!("{ts}"), "2021-10-17T02:03:01.123Z");
+/// assert_eq!(format!("{ts:.6}"), "2021-10-17T02:03:01.123456Z");
+/// assert_eq!(format!("{ts:#.0}"), "20211017T020301Z");
+/// assert_eq!(format!("[{ts:>26.0}]"), "[      2021-10-17T02:03:01Z]");
+/// ```
 impl fmt::Display for Timestamp {
-    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.format())
+        use fmt::Write;
+
+        let expanded = self.format_expanded_fallback();
+
+        if expanded.is_none() && f.precision().is_none() && !f.alternate() && f.width().is_none() {
+            return f.write_str(&self.format());
+        }
+
+        let spec = FormatSpec::DEFAULT
+            .basic_format(f.alternate())
+            .precision(f.precision().map_or(3, |p| p.min(9) as u8));
+
+        let formatted;
+        let ts: &str = match expanded {
+            Some(ref ts) => ts,
+            None => {
+                formatted = self.format_spec(&spec);
+                &formatted
+            }
+        };
+
+        // `Formatter::pad` would treat the precision as a maximum length, so pad by hand
+        let padding = f.width().map_or(0, |width| width.saturating_sub(ts.len()));
+
+        let (pre, post) = match f.align() {
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            _ => (0, padding),
+        };
+
+        let fill = f.fill();
+
+        for _ in 0..pre {
+            f.write_char(fill)?;
+        }
+
+        f.write_str(ts)?;
+
+        for _ in 0..post {
+            f.write_char(fill)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -154,6 +240,8 @@ impl Timestamp {
 pub mod formats {
     use super::*;
 
+    /// `2023-03-24T07:05:59Z`
+    pub type FullSeconds = FormatString<t::True, t::False, t::U0>;
     /// `2023-03-24T07:05:59.005Z`
     pub type FullMilliseconds = FormatString<t::True, t::False, t::U3>;
     /// `2023-03-24T07:05:59.005000Z`
@@ -161,11 +249,56 @@ pub mod formats {
     /// `2023-03-24T07:05:59.005432101Z`
     pub type FullNanoseconds = FormatString<t::True, t::False, t::U9>;
 
+    /// `2023-03-24T07:05:59.00543Z`, with between 0 and 9 fractional digits
+    pub type FullNanosecondsTrimmed = TrimmedFormatString<t::True, t::False, t::U9>;
+
+    /// `2023-03-24T07:05:59+05:00`
+    pub type FullSecondsOffset = FormatString<t::True, t::True, t::U0>;
     /// `2023-03-24T07:05:59.005+05:00`
     pub type FullMillisecondsOffset = FormatString<t::True, t::True, t::U3>;
+    /// `2023-03-24T07:05:59.005000+05:00`
+    pub type FullMicrosecondsOffset = FormatString<t::True, t::True, t::U6>;
+    /// `2023-03-24T07:05:59.005432101+05:00`
+    pub type FullNanosecondsOffset = FormatString<t::True, t::True, t::U9>;
 
+    /// `1847-12-01T00:00:00.000-00:01:15`, or `2023-03-24T07:05:59.005+05:00` when the offset has no seconds
+    pub type FullMillisecondsOffsetSeconds = OffsetSecondsFormatString<t::True, t::U3>;
+
+    /// `20230324T070559Z`, as used by AWS SigV4 for `x-amz-date`
+    pub type ShortSeconds = FormatString<t::False, t::False, t::U0>;
     /// `20230324T070559.005Z`
     pub type ShortMilliseconds = FormatString<t::False, t::False, t::U3>;
+    /// `20230324T070559.005000Z`
+    pub type ShortMicroseconds = FormatString<t::False, t::False, t::U6>;
+    /// `20230324T070559.005432101Z`
+    pub type ShortNanoseconds = FormatString<t::False, t::False, t::U9>;
+
+    /// `20230324T070559+0500`
+    pub type ShortSecondsOffset = FormatString<t::False, t::True, t::U0>;
+    /// `20230324T070559.005+0500`
+    pub type ShortMillisecondsOffset = FormatString<t::False, t::True, t::U3>;
+    /// `20230324T070559.005000+0500`
+    pub type ShortMicrosecondsOffset = FormatString<t::False, t::True, t::U6>;
+    /// `20230324T070559.005432101+0500`
+    pub type ShortNanosecondsOffset = FormatString<t::False, t::True, t::U9>;
+
+    /// `2023-03-24`
+    pub type FullDate = DateFormatString<t::True>;
+    /// `20230324`
+    pub type ShortDate = DateFormatString<t::False>;
+
+    /// `2023-083T07:05:59.005Z`
+    pub type FullOrdinalMilliseconds = OrdinalFormatString<t::True, t::False, t::U3>;
+
+    /// `2023-W12-5T07:05:59.005Z`
+    pub type FullWeekMilliseconds = WeekFormatString<t::True, t::False, t::U3>;
+
+    /// `Fri, 24 Mar 2023 07:05:59 GMT`, the IMF-fixdate format used by HTTP
+    pub type HttpDate = HttpDateFormatString;
+
+    /// `+002023-03-24T07:05:59.005Z`
+    #[cfg(feature = "large-dates")]
+    pub type FullExpandedMilliseconds = ExpandedFormatString<t::True, t::False, t::U3, t::U2>;
 
     #[test]
     #[allow(clippy::assertions_on_constants)]
@@ -224,6 +357,258 @@ impl Timestamp {
         format::do_format(self.0, offset)
     }
 
+    /// Formats the timestamp given the provided formatting parameters directly into the front of `buf`,
+    /// returning the number of bytes written.
+    ///
+    /// Returns `None` without writing anything if `buf` is shorter than
+    /// [`TimestampStr::<FormatString<F, O, P>>::MAX_LEN`](TimestampStr::MAX_LEN), even if the
+    /// formatted timestamp itself would fit.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, UtcOffset, typenum as t};
+    /// let ts = Timestamp::parse("2021-10-17T02:03:01.123Z").unwrap();
+    ///
+    /// let mut buf = [0; 64];
+    /// let len = ts.write_to_slice::<t::True, t::False, t::U3>(UtcOffset::UTC, &mut buf).unwrap();
+    ///
+    /// assert_eq!(&buf[..len], b"2021-10-17T02:03:01.123Z");
+    /// ```
+    #[inline]
+    #[must_use = "returns the number of bytes written"]
+    pub fn write_to_slice<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+        &self,
+        offset: UtcOffset,
+        buf: &mut [u8],
+    ) -> Option<usize>
+    where
+        FormatString<F, O, P>: IsValidFormat,
+    {
+        format::do_format_into::<F, O, P>(self.0, offset, buf)
+    }
+
+    /// Formats the timestamp given the provided formatting parameters into a [`fmt::Write`],
+    /// such as a `String` or [`fmt::Formatter`].
+    ///
+    /// The timestamp is formatted on the stack as with [`Timestamp::format_raw`], then written in one call.
+    #[inline]
+    pub fn write_to_fmt<F: t::Bit, O: t::Bit, P: t::Unsigned, W: fmt::Write + ?Sized>(
+        &self,
+        offset: UtcOffset,
+        w: &mut W,
+    ) -> fmt::Result
+    where
+        FormatString<F, O, P>: IsValidFormat,
+    {
+        w.write_str(&self.format_raw::<F, O, P>(offset))
+    }
+
+    /// Formats the timestamp given the provided formatting parameters into an [`std::io::Write`],
+    /// such as a socket or `Vec<u8>`.
+    ///
+    /// The timestamp is formatted on the stack as with [`Timestamp::format_raw`], then written in one call.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn write_to_io<F: t::Bit, O: t::Bit, P: t::Unsigned, W: std::io::Write + ?Sized>(
+        &self,
+        offset: UtcOffset,
+        w: &mut W,
+    ) -> std::io::Result<()>
+    where
+        FormatString<F, O, P>: IsValidFormat,
+    {
+        w.write_all(self.format_raw::<F, O, P>(offset).as_bytes())
+    }
+
+    /// Formats the timestamp given the provided formatting parameters into a [`bytes::BufMut`],
+    /// directly into its spare capacity when there is enough of it in one piece.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` does not have enough remaining capacity, as with [`bytes::BufMut::put_slice`].
+    #[cfg(feature = "bytes")]
+    pub fn write_to_buf<F: t::Bit, O: t::Bit, P: t::Unsigned, B: bytes::BufMut + ?Sized>(
+        &self,
+        offset: UtcOffset,
+        buf: &mut B,
+    ) where
+        FormatString<F, O, P>: IsValidFormat,
+    {
+        let max_len = TimestampStr::<FormatString<F, O, P>>::MAX_LEN;
+        let chunk = buf.chunk_mut();
+
+        if chunk.len() < max_len {
+            return buf.put_slice(self.format_raw::<F, O, P>(offset).as_bytes());
+        }
+
+        let chunk = &mut chunk[..max_len];
+        chunk.copy_from_slice(ts_str::template::<F, O, P>().as_ref());
+
+        // SAFETY: the chunk was just initialized with the template
+        let len = format::do_format_in_place::<F, O, P>(self.0, offset, unsafe {
+            core::slice::from_raw_parts_mut(chunk.as_mut_ptr(), max_len)
+        });
+
+        // SAFETY: the first `len` bytes of the chunk have been written
+        unsafe { buf.advance_mut(len) };
+    }
+
+    /// Formats the timestamp given the provided formatting parameters, writing the last second
+    /// of the minute as `:60` if `leap_second` is set, as returned by [`Timestamp::parse_with_leap_second`].
+    ///
+    /// Otherwise, or outside of the last second of a minute, this is formatted as with [`Timestamp::format_raw`].
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, UtcOffset, typenum as t};
+    /// let (ts, leap_second) = Timestamp::parse_with_leap_second("2016-12-31T23:59:60.5Z").unwrap();
+    ///
+    /// assert!(leap_second);
+    /// assert_eq!(ts.format_leap_second_raw::<t::True, t::False, t::U1>(UtcOffset::UTC, leap_second), "2016-12-31T23:59:60.5Z");
+    /// ```
+    #[must_use]
+    pub fn format_leap_second_raw<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+        &self,
+        offset: UtcOffset,
+        leap_second: bool,
+    ) -> TimestampStr<FormatString<F, O, P>>
+    where
+        FormatString<F, O, P>: IsValidFormat,
+    {
+        format::do_format_leap_second(self.0, offset, leap_second)
+    }
+
+    /// Formats the timestamp as an ordinal date (`YYYY-DDD`) given the provided formatting parameters
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, UtcOffset, typenum as t};
+    /// let ts = Timestamp::parse("2021-10-17T02:03:01Z").unwrap();
+    ///
+    /// assert_eq!(ts.format_ordinal_raw::<t::False, t::False, t::U0>(UtcOffset::UTC), "2021290T020301Z");
+    /// ```
+    #[must_use]
+    pub fn format_ordinal_raw<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+        &self,
+        offset: UtcOffset,
+    ) -> TimestampStr<OrdinalFormatString<F, O, P>>
+    where
+        OrdinalFormatString<F, O, P>: IsValidFormat,
+    {
+        format::do_format_ordinal(self.0, offset)
+    }
+
+    /// Format timestamp to ISO8601 as an ordinal date (`YYYY-DDD`) with full punctuation, to millisecond precision.
+    #[inline(always)]
+    #[must_use]
+    pub fn format_ordinal(&self) -> TimestampStr<formats::FullOrdinalMilliseconds> {
+        self.format_ordinal_raw(UtcOffset::UTC)
+    }
+
+    /// Formats the timestamp as an ISO week date (`YYYY-Www-D`) given the provided formatting parameters
+    ///
+    /// The year written is the ISO week-numbering year, which may differ from the calendar year
+    /// for the first and last few days of a year.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, UtcOffset, typenum as t};
+    /// let ts = Timestamp::parse("2021-10-17T02:03:01Z").unwrap();
+    ///
+    /// assert_eq!(ts.format_week_raw::<t::False, t::False, t::U0>(UtcOffset::UTC), "2021W417T020301Z");
+    /// ```
+    #[must_use]
+    pub fn format_week_raw<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+        &self,
+        offset: UtcOffset,
+    ) -> TimestampStr<WeekFormatString<F, O, P>>
+    where
+        WeekFormatString<F, O, P>: IsValidFormat,
+    {
+        format::do_format_week(self.0, offset)
+    }
+
+    /// Format timestamp to ISO8601 as a week date (`YYYY-Www-D`) with full punctuation, to millisecond precision.
+    #[inline(always)]
+    #[must_use]
+    pub fn format_week(&self) -> TimestampStr<formats::FullWeekMilliseconds> {
+        self.format_week_raw(UtcOffset::UTC)
+    }
+
+    /// Format timestamp as an IMF-fixdate, the preferred format for HTTP `Date`, `Last-Modified`
+    /// and `Expires` headers, such as `Sun, 17 Oct 2021 02:03:01 GMT`.
+    ///
+    /// Fractional seconds are truncated, and years outside of `0000..=9999` saturate to the first or last
+    /// second that fits.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::Timestamp;
+    /// let ts = Timestamp::parse("2021-10-17T02:03:01.123Z").unwrap();
+    ///
+    /// assert_eq!(ts.format_http_date(), "Sun, 17 Oct 2021 02:03:01 GMT");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn format_http_date(&self) -> TimestampStr<formats::HttpDate> {
+        format::do_format_http_date(self.0)
+    }
+
+    /// Formats the timestamp with an ISO8601 expanded year of `4 + Y` digits, given the provided formatting parameters
+    ///
+    /// Expanded years are always signed. Timestamps with years that do not fit within the given number
+    /// of digits saturate to the first or last timestamp that does.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, UtcOffset, typenum as t};
+    /// let ts = Timestamp::parse("2021-10-17T02:03:01Z").unwrap();
+    ///
+    /// assert_eq!(ts.format_expanded_raw::<t::True, t::False, t::U0, t::U2>(UtcOffset::UTC), "+002021-10-17T02:03:01Z");
+    /// ```
+    #[cfg(feature = "large-dates")]
+    #[must_use]
+    pub fn format_expanded_raw<F: t::Bit, O: t::Bit, P: t::Unsigned, Y: t::Unsigned>(
+        &self,
+        offset: UtcOffset,
+    ) -> TimestampStr<ExpandedFormatString<F, O, P, Y>>
+    where
+        FormatString<F, O, P>: IsValidFormat,
+        ExpandedFormatString<F, O, P, Y>: IsValidFormat,
+    {
+        format::do_format_expanded(self.0, offset)
+    }
+
+    /// Format timestamp to ISO8601 with a 6-digit expanded year and full punctuation, to millisecond precision.
+    #[cfg(feature = "large-dates")]
+    #[inline(always)]
+    #[must_use]
+    pub fn format_expanded(&self) -> TimestampStr<formats::FullExpandedMilliseconds> {
+        self.format_expanded_raw(UtcOffset::UTC)
+    }
+
+    /// For years beyond ±9999, which 4-digit formats saturate, the expanded form with nanosecond precision,
+    /// so that `Debug`, `Display` and serialization can write the timestamp in full.
+    #[cfg(feature = "large-dates")]
+    #[inline]
+    pub(crate) fn format_expanded_fallback(
+        &self,
+    ) -> Option<TimestampStr<ExpandedFormatString<t::True, t::False, t::U9, t::U2>>> {
+        match unlikely!(self.0.year().unsigned_abs() > 9999) {
+            true => Some(self.format_expanded_raw(UtcOffset::UTC)),
+            false => None,
+        }
+    }
+
+    /// Every year fits within 4 digits without `large-dates`.
+    #[cfg(not(feature = "large-dates"))]
+    #[inline(always)]
+    pub(crate) fn format_expanded_fallback(&self) -> Option<TimestampStr<formats::FullNanoseconds>> {
+        None
+    }
+
+    /// Parses as with [`Timestamp::try_parse`], falling back to the 6-digit expanded years
+    /// written by [`Timestamp::format_expanded_fallback`].
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub(crate) fn try_parse_or_expanded(ts: &str) -> Result<Self, ParseError> {
+        parse::parse_iso8601_or_expanded(ts.as_bytes()).map(Timestamp)
+    }
+
     /// Formats a full timestamp without offset, using the given subsecond precision level.
     #[inline(always)]
     #[must_use]
@@ -234,6 +619,72 @@ impl Timestamp {
         self.format_raw(UtcOffset::UTC)
     }
 
+    /// Formats the timestamp given the provided formatting parameters, with at most `P` fractional digits
+    /// and trailing zeros removed as described by the [`TrimFraction`] mode. No allocation is needed,
+    /// as the shorter string is kept within the same inline storage.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, TrimFraction, UtcOffset, typenum as t};
+    /// let ts = Timestamp::parse("2021-10-17T02:03:01.1204Z").unwrap();
+    /// let offset = UtcOffset::from_hms(10, 0, 0).unwrap();
+    ///
+    /// assert_eq!(ts.format_trimmed_raw::<t::True, t::True, t::U6>(offset, TrimFraction::Zeros), "2021-10-17T02:03:01.1204+10:00");
+    /// assert_eq!(ts.format_trimmed_raw::<t::True, t::False, t::U3>(UtcOffset::UTC, TrimFraction::Zeros), "2021-10-17T02:03:01.12Z");
+    /// assert_eq!(ts.format_trimmed_raw::<t::False, t::False, t::U9>(UtcOffset::UTC, TrimFraction::Groups), "20211017T020301.120400Z");
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn format_trimmed_raw<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+        &self,
+        offset: UtcOffset,
+        trim: TrimFraction,
+    ) -> TimestampStr<TrimmedFormatString<F, O, P>>
+    where
+        FormatString<F, O, P>: IsValidFormat,
+        TrimmedFormatString<F, O, P>: IsValidFormat<
+            Storage = ts_str::VarStorage<<TrimmedFormatString<F, O, P> as IsValidFormat>::Length>,
+        >,
+    {
+        format::do_format_trimmed(self.0, offset, trim)
+    }
+
+    /// Format timestamp to ISO8601 with up to nanosecond precision, removing any trailing zeros
+    /// like Go's `RFC3339Nano`, so whole seconds have no fraction at all.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::Timestamp;
+    /// assert_eq!(Timestamp::parse("2021-10-17T02:03:01.12Z").unwrap().format_trimmed(), "2021-10-17T02:03:01.12Z");
+    /// assert_eq!(Timestamp::parse("2021-10-17T02:03:01Z").unwrap().format_trimmed(), "2021-10-17T02:03:01Z");
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn format_trimmed(&self) -> TimestampStr<formats::FullNanosecondsTrimmed> {
+        self.format_trimmed_raw(UtcOffset::UTC, TrimFraction::Zeros)
+    }
+
+    /// Format timestamp to ISO8601 with 0, 3, 6 or 9 fractional digits, whichever is the fewest
+    /// without losing precision, as expected by protobuf JSON and JavaScript.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::Timestamp;
+    /// assert_eq!(Timestamp::parse("2021-10-17T02:03:01.12Z").unwrap().format_trimmed_to_groups(), "2021-10-17T02:03:01.120Z");
+    /// assert_eq!(Timestamp::parse("2021-10-17T02:03:01.1234Z").unwrap().format_trimmed_to_groups(), "2021-10-17T02:03:01.123400Z");
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn format_trimmed_to_groups(&self) -> TimestampStr<formats::FullNanosecondsTrimmed> {
+        self.format_trimmed_raw(UtcOffset::UTC, TrimFraction::Groups)
+    }
+
+    /// Formats the timestamp as described by a [`FormatSpec`] chosen at runtime, into an inline string
+    /// large enough for any spec. The typed formats, such as [`Timestamp::format_raw`], are slightly faster
+    /// when the format is known at compile time.
+    #[inline]
+    #[must_use]
+    pub fn format_spec(&self, spec: &FormatSpec) -> TimestampStr<SpecFormatString> {
+        format::do_format_spec(self.0, spec)
+    }
+
     /// Format timestamp to ISO8601 with full punctuation, to millisecond precision.
     #[inline(always)]
     #[must_use]
@@ -262,6 +713,91 @@ impl Timestamp {
         self.format_raw(UtcOffset::UTC)
     }
 
+    /// Format timestamp to ISO8601 without most punctuation, and the provided level of subsecond precision.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, typenum as t};
+    /// let ts = Timestamp::parse("2021-10-17T02:03:01.123Z").unwrap();
+    ///
+    /// // AWS SigV4 `x-amz-date`
+    /// assert_eq!(ts.format_short_with_precision::<t::U0>(), "20211017T020301Z");
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn format_short_with_precision<P: t::Unsigned>(
+        &self,
+    ) -> TimestampStr<FormatString<t::False, t::False, P>>
+    where
+        FormatString<t::False, t::False, P>: IsValidFormat,
+    {
+        self.format_raw(UtcOffset::UTC)
+    }
+
+    /// Format timestamp to ISO8601 without most punctuation, to millisecond precision, with arbitrary UTC offset
+    /// formatted as `+HHMM`. As with [`Timestamp::format_with_offset`], no timezone conversions are done.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, UtcOffset};
+    /// let ts = Timestamp::parse("2021-10-17T02:03:01Z").unwrap();
+    /// let offset = UtcOffset::from_hms(-4, -30, 0).unwrap();
+    ///
+    /// assert_eq!(ts.format_short_with_offset(offset), "20211017T020301.000-0430");
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn format_short_with_offset(
+        &self,
+        offset: UtcOffset,
+    ) -> TimestampStr<formats::ShortMillisecondsOffset> {
+        self.format_raw(offset)
+    }
+
+    /// Formats a timestamp without most punctuation with timezone offset, and the provided level of subsecond precision.
+    #[inline(always)]
+    #[must_use]
+    pub fn format_short_with_offset_and_precision<P: t::Unsigned>(
+        &self,
+        offset: UtcOffset,
+    ) -> TimestampStr<FormatString<t::False, t::True, P>>
+    where
+        FormatString<t::False, t::True, P>: IsValidFormat,
+    {
+        self.format_raw(offset)
+    }
+
+    /// Formats only the date of the timestamp, given the provided formatting parameters.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, typenum as t};
+    /// let ts = Timestamp::parse("2021-10-17T02:03:01Z").unwrap();
+    ///
+    /// assert_eq!(ts.format_date_raw::<t::True>(), "2021-10-17");
+    /// assert_eq!(ts.format_date_raw::<t::False>(), "20211017");
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn format_date_raw<F: t::Bit>(&self) -> TimestampStr<DateFormatString<F>>
+    where
+        FormatString<F, t::False, t::U0>: IsValidFormat,
+        DateFormatString<F>: IsValidFormat,
+    {
+        format::do_format_date(self.0)
+    }
+
+    /// Format only the date of the timestamp to ISO8601 with full punctuation, as `YYYY-MM-DD`.
+    #[inline(always)]
+    #[must_use]
+    pub fn format_date(&self) -> TimestampStr<formats::FullDate> {
+        self.format_date_raw()
+    }
+
+    /// Format only the date of the timestamp to ISO8601 without punctuation, as `YYYYMMDD`.
+    #[inline(always)]
+    #[must_use]
+    pub fn format_short_date(&self) -> TimestampStr<formats::ShortDate> {
+        self.format_date_raw()
+    }
+
     /// Format timestamp to ISO8601 with arbitrary UTC offset. Any offset is formatted as `+HH:MM`,
     /// and no timezone conversions are done. It is interpreted literally.
     #[inline(always)]
@@ -270,6 +806,43 @@ impl Timestamp {
         self.format_raw(offset)
     }
 
+    /// Formats the timestamp with a UTC offset that includes seconds when they are non-zero,
+    /// given the provided formatting parameters. As with [`Timestamp::format_with_offset`],
+    /// the offset is written literally and no conversion is done.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, UtcOffset, typenum as t};
+    /// let ts = Timestamp::parse("1847-12-01T00:00:00Z").unwrap();
+    ///
+    /// let lmt = UtcOffset::from_hms(0, -1, -15).unwrap();
+    /// assert_eq!(ts.format_offset_seconds_raw::<t::True, t::U0>(lmt), "1847-12-01T00:00:00-00:01:15");
+    /// assert_eq!(ts.format_offset_seconds_raw::<t::True, t::U0>(UtcOffset::UTC), "1847-12-01T00:00:00+00:00");
+    /// ```
+    #[must_use]
+    pub fn format_offset_seconds_raw<F: t::Bit, P: t::Unsigned>(
+        &self,
+        offset: UtcOffset,
+    ) -> TimestampStr<OffsetSecondsFormatString<F, P>>
+    where
+        FormatString<F, t::True, P>: IsValidFormat,
+        OffsetSecondsFormatString<F, P>: IsValidFormat<
+            Storage = ts_str::VarStorage<<OffsetSecondsFormatString<F, P> as IsValidFormat>::Length>,
+        >,
+    {
+        format::do_format_offset_seconds(self.0, offset)
+    }
+
+    /// Format timestamp to ISO8601 with arbitrary UTC offset, like [`Timestamp::format_with_offset`],
+    /// but formatted as `+HH:MM:SS` when the offset has non-zero seconds rather than truncating them.
+    #[inline(always)]
+    #[must_use]
+    pub fn format_with_offset_seconds(
+        &self,
+        offset: UtcOffset,
+    ) -> TimestampStr<formats::FullMillisecondsOffsetSeconds> {
+        self.format_offset_seconds_raw(offset)
+    }
+
     /// Formats a full timestamp with timezone offset, and the provided level of subsecond precision.
     #[inline(always)]
     #[must_use]
@@ -283,13 +856,363 @@ impl Timestamp {
         self.format_raw(offset)
     }
 
+    /// Formats a full timestamp as the local time at the offset given by [`Timestamp::parse_with_offset`],
+    /// with the provided level of subsecond precision.
+    ///
+    /// Unlike [`Timestamp::format_with_offset`], the UTC timestamp is first converted to the local time,
+    /// so parsing the result gives back the same timestamp and offset. [`ParsedOffset::UnknownLocal`] is written
+    /// as `-00:00`, [`ParsedOffset::Utc`] as `Z`, and [`ParsedOffset::Unspecified`] without any offset.
+    ///
+    /// If the local time would be out of range, or have a year beyond 4 digits, the timestamp is written
+    /// in UTC with `Z` instead, which still parses back to the same timestamp.
+    #[must_use]
+    pub fn format_at_offset<P: t::Unsigned>(
+        &self,
+        offset: ParsedOffset,
+    ) -> TimestampStr<OffsetSecondsFormatString<t::True, P>>
+    where
+        FormatString<t::True, t::True, P>: IsValidFormat,
+        OffsetSecondsFormatString<t::True, P>: IsValidFormat<
+            Storage = ts_str::VarStorage<<OffsetSecondsFormatString<t::True, P> as IsValidFormat>::Length>,
+        >,
+    {
+        let local = self
+            .0
+            .checked_add(Duration::seconds(offset.utc_offset().whole_seconds() as i64));
+
+        let (local, offset) = match local.filter(|local| local.year().unsigned_abs() <= 9999) {
+            Some(local) => (local, offset),
+            None => (self.0, ParsedOffset::Utc),
+        };
+
+        let utc_offset = offset.utc_offset();
+
+        let mut ts = format::do_format_offset_seconds(local, utc_offset);
+        let sign = ts.0.len as usize - "+00:00".len();
+
+        match offset {
+            ParsedOffset::Fixed(_) => {}
+            ParsedOffset::UnknownLocal => ts.0.buf[sign] = b'-',
+            ParsedOffset::Utc => {
+                ts.0.buf[sign] = b'Z';
+                ts.0.len = sign as u8 + 1;
+            }
+            ParsedOffset::Unspecified => ts.0.len = sign as u8,
+        }
+
+        ts
+    }
+
     /// Parse to UTC timestamp from any ISO8601 string. Offsets are applied during parsing.
     #[inline(never)]
     #[must_use] // Avoid deoptimizing the general &str case when presented with a fixed-size string
     pub fn parse(ts: &str) -> Option<Self> {
+        parse::parse_iso8601(ts.as_bytes()).ok().map(Timestamp)
+    }
+
+    /// Parse to UTC timestamp from any ISO8601 byte string, like [`Timestamp::parse`],
+    /// for buffers that are not known to be valid UTF-8.
+    #[inline(never)]
+    #[must_use]
+    pub fn parse_bytes(ts: &[u8]) -> Option<Self> {
+        parse::parse_iso8601(ts).ok().map(Timestamp)
+    }
+
+    /// Parse a UTC timestamp from the start of a byte string, returning it
+    /// along with the number of bytes consumed. Any bytes that cannot continue the
+    /// timestamp end it, and are not required to be valid UTF-8.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::Timestamp;
+    /// let line = b"2021-10-17T02:03:01.123Z INFO request completed";
+    /// let (ts, len) = Timestamp::parse_prefix(line).unwrap();
+    ///
+    /// assert_eq!(ts, Timestamp::parse("2021-10-17T02:03:01.123Z").unwrap());
+    /// assert_eq!(&line[len..], b" INFO request completed");
+    /// ```
+    #[inline(never)]
+    #[must_use]
+    pub fn parse_prefix(ts: &[u8]) -> Option<(Self, usize)> {
+        const OPTIONS: ParseOptions = ParseOptions::LENIENT.prefix();
+
+        match parse::parse_iso8601_with(ts, &OPTIONS) {
+            Ok(parsed) => Some((Timestamp(parsed.date_time), parsed.len)),
+            Err(_) => None,
+        }
+    }
+
+    /// Like [`Timestamp::parse_prefix`], but only accepting the variations allowed by the given [`ParseOptions`],
+    /// and returning a [`ParseError`] describing why and where parsing failed.
+    #[inline(never)]
+    pub fn parse_prefix_with_options(ts: &[u8], opts: &ParseOptions) -> Result<(Self, usize), ParseError> {
+        parse::parse_iso8601_with(ts, &opts.prefix()).map(|parsed| (Timestamp(parsed.date_time), parsed.len))
+    }
+
+    /// Parse a column of timestamps at once, like calling [`Timestamp::parse`] on each value,
+    /// writing the results to the matching positions of `out`.
+    ///
+    /// Runs of values that share a layout, such as `2021-10-17T02:03:01.123Z`, are spotted
+    /// and parsed with a fixed-layout fast path, several values at a time where SIMD allows.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::Timestamp;
+    /// let column = ["2021-10-17T02:03:01.123Z", "2021-10-17T02:03:02.456Z", "invalid"];
+    /// let mut out = [None; 3];
+    ///
+    /// Timestamp::parse_batch(&column, &mut out);
+    ///
+    /// assert_eq!(out, [Timestamp::parse(column[0]), Timestamp::parse(column[1]), None]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` and `out` have different lengths.
+    #[inline(never)]
+    pub fn parse_batch(inputs: &[&str], out: &mut [Option<Timestamp>]) {
+        assert_eq!(inputs.len(), out.len(), "input and output lengths differ");
+
+        parse::batch::parse_batch(|i| inputs.get(i).map(|s| s.as_bytes()), out);
+    }
+
+    /// Parse a column of timestamps stored as one contiguous buffer, like Arrow string arrays,
+    /// where value `i` is `values[offsets[i]..offsets[i + 1]]`. Otherwise the same as [`Timestamp::parse_batch`].
+    ///
+    /// Offsets that are negative, out of bounds or decreasing give `None` for that value.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::Timestamp;
+    /// let values = b"2021-10-17T02:03:01Z2021-10-17T02:03:02Z";
+    /// let offsets: [i32; 3] = [0, 20, 40];
+    /// let mut out = [None; 2];
+    ///
+    /// Timestamp::parse_batch_offsets(&offsets, values, &mut out);
+    ///
+    /// assert_eq!(out[1], Timestamp::parse("2021-10-17T02:03:02Z"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offsets` is not one longer than `out`.
+    #[inline(never)]
+    pub fn parse_batch_offsets<O>(offsets: &[O], values: &[u8], out: &mut [Option<Timestamp>])
+    where
+        O: Copy + core::convert::TryInto<usize>,
+    {
+        assert_eq!(
+            offsets.len(),
+            out.len() + 1,
+            "offsets must be one longer than the output"
+        );
+
+        parse::batch::parse_batch(
+            |i| {
+                let start = offsets[i].try_into().ok()?;
+                let end = offsets[i + 1].try_into().ok()?;
+                values.get(start..end)
+            },
+            out,
+        );
+    }
+
+    /// Parse to UTC timestamp from any ISO8601 string, like [`Timestamp::parse`],
+    /// but returns a [`ParseError`] describing why and where parsing failed.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, ParseErrorKind};
+    /// let err = Timestamp::try_parse("2021-13-17T02:03:01Z").unwrap_err();
+    ///
+    /// assert_eq!(err.kind(), ParseErrorKind::MonthOutOfRange);
+    /// assert_eq!(err.offset(), 5);
+    /// ```
+    #[inline(never)]
+    pub fn try_parse(ts: &str) -> Result<Self, ParseError> {
         parse::parse_iso8601(ts.as_bytes()).map(Timestamp)
     }
 
+    /// Parse to UTC timestamp from an RFC 2822 date, as used by email `Date` headers, with a numeric
+    /// or named zone, or from any of the HTTP-date formats: IMF-fixdate, RFC 850 and asctime.
+    ///
+    /// Names are matched without regard to case, and a named weekday must match the date.
+    /// Two-digit years are 2000-2049 when below 50, and 1950-1999 otherwise, as in RFC 2822.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::Timestamp;
+    /// let ts = Timestamp::parse("2021-10-17T02:03:01Z");
+    ///
+    /// assert_eq!(Timestamp::parse_rfc2822("Sun, 17 Oct 2021 02:03:01 GMT"), ts);
+    /// assert_eq!(Timestamp::parse_rfc2822("Sun, 17 Oct 2021 12:03:01 +1000"), ts);
+    /// assert_eq!(Timestamp::parse_rfc2822("Sat, 16 Oct 2021 22:03:01 EDT"), ts);
+    /// assert_eq!(Timestamp::parse_rfc2822("Sunday, 17-Oct-21 02:03:01 GMT"), ts);
+    /// assert_eq!(Timestamp::parse_rfc2822("Sun Oct 17 02:03:01 2021"), ts);
+    /// ```
+    #[inline(never)]
+    #[must_use]
+    pub fn parse_rfc2822(ts: &str) -> Option<Self> {
+        parse::rfc2822::parse_rfc2822(ts.as_bytes()).ok().map(Timestamp)
+    }
+
+    /// Like [`Timestamp::parse_rfc2822`], but returns a [`ParseError`] describing why and where parsing failed.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, ParseErrorKind};
+    /// let err = Timestamp::try_parse_rfc2822("Mon, 17 Oct 2021 02:03:01 GMT").unwrap_err();
+    ///
+    /// assert_eq!(err.kind(), ParseErrorKind::WeekdayMismatch);
+    /// assert_eq!(err.offset(), 0);
+    /// ```
+    #[inline(never)]
+    pub fn try_parse_rfc2822(ts: &str) -> Result<Self, ParseError> {
+        parse::rfc2822::parse_rfc2822(ts.as_bytes()).map(Timestamp)
+    }
+
+    /// Parse to UTC timestamp, accepting only the variations allowed by the given [`ParseOptions`].
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, ParseOptions};
+    /// assert!(Timestamp::parse_with_options("2021-10-17T02:03:01Z", &ParseOptions::RFC3339).is_ok());
+    /// assert!(Timestamp::parse_with_options("20211017T020301Z", &ParseOptions::RFC3339).is_err());
+    /// assert!(Timestamp::parse_with_options("2021-10-17T02:03", &ParseOptions::HTML_DATETIME_LOCAL).is_ok());
+    /// ```
+    #[inline(never)]
+    pub fn parse_with_options(ts: &str, opts: &ParseOptions) -> Result<Self, ParseError> {
+        parse::parse_iso8601_with(ts.as_bytes(), opts).map(|parsed| Timestamp(parsed.date_time))
+    }
+
+    /// Parse to UTC timestamp from any ISO8601 string, also returning the offset that was
+    /// applied during parsing, or what kind of timezone designator was present.
+    ///
+    /// The original representation can be recovered with [`Timestamp::format_at_offset`].
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, ParsedOffset, typenum as t};
+    /// let (ts, offset) = Timestamp::parse_with_offset("2021-10-17T02:03:01+10:00").unwrap();
+    ///
+    /// assert_eq!(ts, Timestamp::parse("2021-10-16T16:03:01Z").unwrap());
+    /// assert_eq!(offset.utc_offset(), time::macros::offset!(+10:00));
+    /// assert_eq!(ts.format_at_offset::<t::U0>(offset), "2021-10-17T02:03:01+10:00");
+    /// ```
+    #[inline(never)]
+    pub fn parse_with_offset(ts: &str) -> Result<(Self, ParsedOffset), ParseError> {
+        Timestamp::parse_with_offset_and_options(ts, &ParseOptions::LENIENT)
+    }
+
+    /// Like [`Timestamp::parse_with_offset`], but only accepting the variations allowed by the given [`ParseOptions`].
+    #[inline(never)]
+    pub fn parse_with_offset_and_options(
+        ts: &str,
+        opts: &ParseOptions,
+    ) -> Result<(Self, ParsedOffset), ParseError> {
+        parse::parse_iso8601_with(ts.as_bytes(), opts)
+            .map(|parsed| (Timestamp(parsed.date_time), parsed.offset))
+    }
+
+    /// Parse to UTC timestamp from any ISO8601 string followed by RFC 9557 annotations, also returning
+    /// the offset that was applied, and the annotations, such as a time zone name or calendar.
+    ///
+    /// Annotations are checked against the RFC 9557 grammar, but are otherwise left to the caller,
+    /// who should reject any [critical](Annotation::critical) annotations they cannot act upon.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Annotation, Timestamp};
+    /// let (ts, offset, annotations) =
+    ///     Timestamp::parse_with_annotations("2021-10-17T02:03:01+02:00[Europe/Paris][u-ca=gregory]").unwrap();
+    ///
+    /// assert_eq!(ts, Timestamp::parse("2021-10-17T00:03:01Z").unwrap());
+    /// assert_eq!(annotations.time_zone().map(|tz| tz.value), Some("Europe/Paris"));
+    /// assert_eq!(annotations.get("u-ca").map(|tag| tag.value), Some("gregory"));
+    /// ```
+    #[inline(never)]
+    pub fn parse_with_annotations(ts: &str) -> Result<(Self, ParsedOffset, Annotations<'_>), ParseError> {
+        Timestamp::parse_with_annotations_and_options(ts, &ParseOptions::LENIENT)
+    }
+
+    /// Like [`Timestamp::parse_with_annotations`], but only accepting the variations allowed by the given
+    /// [`ParseOptions`], which must [allow annotations](ParseOptions::allow_annotations) for any to be found.
+    #[inline(never)]
+    pub fn parse_with_annotations_and_options<'a>(
+        ts: &'a str,
+        opts: &ParseOptions,
+    ) -> Result<(Self, ParsedOffset, Annotations<'a>), ParseError> {
+        let parsed = parse::parse_iso8601_with(ts.as_bytes(), &opts.keep_annotations())?;
+
+        // timestamps never contain brackets, so the annotations start at the first
+        let start = ts.find('[').unwrap_or(ts.len());
+
+        Ok((
+            Timestamp(parsed.date_time),
+            parsed.offset,
+            Annotations::new(&ts[start..]),
+        ))
+    }
+
+    /// Parse to UTC timestamp from any ISO8601 string, also returning the [`Precision`] of the input.
+    ///
+    /// Reduced-precision inputs expand to the start of the period they name.
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::{Timestamp, Precision};
+    /// let (ts, precision) = Timestamp::parse_with_precision("2021-10").unwrap();
+    ///
+    /// assert_eq!(ts, Timestamp::parse("2021-10-01T00:00:00Z").unwrap());
+    /// assert_eq!(precision, Precision::Month);
+    ///
+    /// let (ts, precision) = Timestamp::parse_with_precision("2021-10-17T02:03.5Z").unwrap();
+    ///
+    /// assert_eq!(ts, Timestamp::parse("2021-10-17T02:03:30Z").unwrap());
+    /// assert_eq!(precision, Precision::FractionalMinute(1));
+    /// ```
+    #[inline(never)]
+    pub fn parse_with_precision(ts: &str) -> Result<(Self, Precision), ParseError> {
+        Timestamp::parse_with_precision_and_options(ts, &ParseOptions::LENIENT)
+    }
+
+    /// Like [`Timestamp::parse_with_precision`], but only accepting the variations allowed by the given [`ParseOptions`].
+    #[inline(never)]
+    pub fn parse_with_precision_and_options(
+        ts: &str,
+        opts: &ParseOptions,
+    ) -> Result<(Self, Precision), ParseError> {
+        parse::parse_iso8601_with(ts.as_bytes(), opts)
+            .map(|parsed| (Timestamp(parsed.date_time), parsed.precision))
+    }
+
+    /// Parse to UTC timestamp from any ISO8601 string, also returning whether the input was a leap second.
+    ///
+    /// Leap seconds are preserved as described in [`ParseOptions::preserve_leap_seconds`],
+    /// and can be written back out with [`Timestamp::format_leap_second_raw`].
+    ///
+    /// ```rust
+    /// # use iso8601_timestamp::Timestamp;
+    /// let (ts, leap_second) = Timestamp::parse_with_leap_second("2016-12-31T23:59:60.5Z").unwrap();
+    ///
+    /// assert!(leap_second);
+    /// assert_eq!(ts, Timestamp::parse("2016-12-31T23:59:59.5Z").unwrap());
+    ///
+    /// let (_, leap_second) = Timestamp::parse_with_leap_second("2016-12-31T23:59:59.999999999Z").unwrap();
+    ///
+    /// assert!(!leap_second);
+    /// assert!(Timestamp::parse_with_leap_second("2016-12-31T23:59:61Z").is_err());
+    /// ```
+    #[inline(never)]
+    pub fn parse_with_leap_second(ts: &str) -> Result<(Self, bool), ParseError> {
+        const OPTIONS: ParseOptions = ParseOptions::LENIENT.preserve_leap_seconds(true);
+
+        Timestamp::parse_with_leap_second_and_options(ts, &OPTIONS)
+    }
+
+    /// Like [`Timestamp::parse_with_leap_second`], but only accepting the variations allowed by the given [`ParseOptions`].
+    ///
+    /// Leap seconds are only preserved if [`ParseOptions::preserve_leap_seconds`] is set,
+    /// but are always reported.
+    #[inline(never)]
+    pub fn parse_with_leap_second_and_options(
+        ts: &str,
+        opts: &ParseOptions,
+    ) -> Result<(Self, bool), ParseError> {
+        parse::parse_iso8601_with(ts.as_bytes(), opts)
+            .map(|parsed| (Timestamp(parsed.date_time), parsed.leap_second.is_some()))
+    }
+
     /// Convert to `time::OffsetDateTime` with the given offset.
     #[inline(always)]
     #[must_use]
@@ -340,6 +1263,24 @@ impl Timestamp {
     }
 }
 
+impl core::str::FromStr for Timestamp {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Timestamp::try_parse(s)
+    }
+}
+
+impl core::convert::TryFrom<&str> for Timestamp {
+    type Error = ParseError;
+
+    #[inline]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Timestamp::try_parse(value)
+    }
+}
+
 impl Deref for Timestamp {
     type Target = PrimitiveDateTime;
 
@@ -404,7 +1345,7 @@ where
 
 #[cfg(feature = "serde")]
 mod serde_impl {
-    use serde_core::de::{Deserialize, Deserializer, Error, Visitor};
+    use serde_core::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
     use serde_core::ser::{Serialize, Serializer};
 
     #[cfg(feature = "bson")]
@@ -419,7 +1360,10 @@ mod serde_impl {
             S: Serializer,
         {
             if serializer.is_human_readable() {
-                self.format().serialize(serializer)
+                match self.format_expanded_fallback() {
+                    Some(ts) => serializer.serialize_str(&ts),
+                    None => self.format().serialize(serializer),
+                }
             } else {
                 (self.duration_since(Timestamp::UNIX_EPOCH).whole_milliseconds() as i64).serialize(serializer)
             }
@@ -452,10 +1396,7 @@ mod serde_impl {
                 where
                     E: Error,
                 {
-                    match Timestamp::parse(v) {
-                        Some(ts) => Ok(ts),
-                        None => Err(E::custom("Invalid Format")),
-                    }
+                    Timestamp::try_parse_or_expanded(v).map_err(E::custom)
                 }
 
                 #[cfg(feature = "bson")]
@@ -492,6 +1433,15 @@ mod serde_impl {
                     }
                 }
 
+                // `(seconds, nanoseconds)` as written by `serde::lossless`
+                #[inline]
+                fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+                where
+                    A: SeqAccess<'de>,
+                {
+                    crate::serde::visit_parts(seq)
+                }
+
                 #[inline]
                 fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
                 where
@@ -677,32 +1627,19 @@ mod rusqlite_impl {
     use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, Value, ValueRef};
     use rusqlite::Error;
 
-    #[derive(Debug)]
-    struct InvalidTimestamp;
-
-    use core::{error, fmt, str};
+    use core::str;
 
     extern crate alloc;
 
     use alloc::borrow::ToOwned;
 
-    impl error::Error for InvalidTimestamp {}
-    impl fmt::Display for InvalidTimestamp {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            f.write_str("Invalid ISO8601 Timestamp")
-        }
-    }
-
     impl FromSql for Timestamp {
         fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
             // https://www.sqlite.org/lang_datefunc.html
             match value {
                 ValueRef::Text(bytes) => match str::from_utf8(bytes) {
                     Err(e) => Err(FromSqlError::Other(Error::Utf8Error(e).into())),
-                    Ok(ts) => match Timestamp::parse(ts) {
-                        Some(ts) => Ok(ts),
-                        None => Err(FromSqlError::Other(InvalidTimestamp.into())),
-                    },
+                    Ok(ts) => Timestamp::try_parse(ts).map_err(|e| FromSqlError::Other(e.into())),
                 },
 
                 // according to the link above, dates stored as integers are seconds since unix epoch
@@ -931,7 +1868,15 @@ mod fred_impl {
         types::{Expiration, FromKey, FromValue, Key, Value},
     };
 
-    use super::{Duration, Timestamp};
+    use super::{Duration, ParseError, Timestamp};
+
+    extern crate alloc;
+
+    use alloc::string::ToString;
+
+    fn parse_error(err: ParseError) -> Error {
+        Error::new(ErrorKind::Parse, err.to_string())
+    }
 
     impl From<Timestamp> for Value {
         fn from(ts: Timestamp) -> Self {
@@ -948,11 +1893,9 @@ mod fred_impl {
     impl FromValue for Timestamp {
         fn from_value(value: Value) -> Result<Self, Error> {
             match value {
-                Value::String(ts) => Timestamp::parse(&ts)
-                    .ok_or_else(|| Error::new(ErrorKind::Parse, "Invalid Timestamp format")),
+                Value::String(ts) => Timestamp::try_parse(&ts).map_err(parse_error),
                 Value::Bytes(ts) => match core::str::from_utf8(&ts) {
-                    Ok(ts) => Timestamp::parse(ts)
-                        .ok_or_else(|| Error::new(ErrorKind::Parse, "Invalid Timestamp format")),
+                    Ok(ts) => Timestamp::try_parse(ts).map_err(parse_error),
                     Err(_) => Err(Error::new(ErrorKind::Parse, "Invalid UTF-8 Timestamp")),
                 },
                 Value::Integer(ts) => Timestamp::UNIX_EPOCH
@@ -969,7 +1912,7 @@ mod fred_impl {
                 return Err(Error::new(ErrorKind::Parse, "Invalid UTF-8 Key"));
             };
 
-            Timestamp::parse(value).ok_or_else(|| Error::new(ErrorKind::Parse, "Invalid Timestamp format"))
+            Timestamp::try_parse(value).map_err(parse_error)
         }
     }
 
@@ -0,0 +1,402 @@
+/// How the parser treats the timezone designator at the end of a timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimezoneRule {
+    /// A `Z` or `±hh:mm` offset must be present.
+    Required,
+    /// The timezone may be omitted, in which case UTC is assumed.
+    Optional,
+    /// No timezone may be present, as with local date-times.
+    Forbidden,
+}
+
+/// Options controlling which ISO8601 variations are accepted by the parser.
+///
+/// Several preset profiles are provided, such as [`ParseOptions::RFC3339`], and can be further
+/// customized using the builder methods:
+///
+/// ```rust
+/// # use iso8601_timestamp::{ParseOptions, Timestamp, TimezoneRule};
+/// let opts = ParseOptions::RFC3339.allow_space_separator(true);
+///
+/// assert!(Timestamp::parse_with_options("2021-10-17 02:03:01Z", &opts).is_ok());
+/// assert!(Timestamp::parse_with_options("2021-10-17 02:03:01", &opts).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    pub(crate) lowercase: bool,
+    pub(crate) space_separator: bool,
+    pub(crate) underscore_separator: bool,
+    pub(crate) basic_format: bool,
+    pub(crate) ordinal_dates: bool,
+    pub(crate) week_dates: bool,
+    pub(crate) expanded_year_digits: u8,
+    pub(crate) plus_sign: bool,
+    pub(crate) minus_sign: bool,
+    pub(crate) unicode_minus: bool,
+    pub(crate) date_only: bool,
+    pub(crate) reduced_precision: bool,
+    pub(crate) require_seconds: bool,
+    pub(crate) end_of_day: bool,
+    pub(crate) leap_seconds: bool,
+    pub(crate) decimal_comma: bool,
+    pub(crate) min_fraction_digits: usize,
+    pub(crate) max_fraction_digits: usize,
+    pub(crate) timezone: TimezoneRule,
+    pub(crate) offset_hours_only: bool,
+    pub(crate) offset_seconds: bool,
+    pub(crate) utc_suffix: bool,
+    pub(crate) check_digits: bool,
+    pub(crate) annotations: bool,
+    pub(crate) keep_annotations: bool,
+    pub(crate) prefix: bool,
+}
+
+impl Default for ParseOptions {
+    #[inline]
+    fn default() -> Self {
+        ParseOptions::LENIENT
+    }
+}
+
+impl ParseOptions {
+    /// The permissive default used by [`Timestamp::parse`](crate::Timestamp::parse).
+    ///
+    /// Accepts `T`, `t`, space or `_` separators, basic and extended formats, ordinal and week dates,
+    /// reduced precision with fractional hours or minutes, `24:00` as the end of the day, an optional timezone,
+    /// `±hh`/`±hh:mm:ss` offsets, `,` decimals, a trailing `UTC`, Unicode minus signs, any number of fractional digits and elective RFC 9557 annotations.
+    pub const LENIENT: Self = ParseOptions {
+        lowercase: true,
+        space_separator: true,
+        underscore_separator: true,
+        basic_format: true,
+        ordinal_dates: true,
+        week_dates: true,
+        expanded_year_digits: 0,
+        plus_sign: true,
+        minus_sign: true,
+        unicode_minus: true,
+        date_only: true,
+        reduced_precision: true,
+        require_seconds: false,
+        end_of_day: true,
+        leap_seconds: false,
+        decimal_comma: true,
+        min_fraction_digits: 0,
+        max_fraction_digits: usize::MAX,
+        timezone: TimezoneRule::Optional,
+        offset_hours_only: true,
+        offset_seconds: true,
+        utc_suffix: true,
+        check_digits: false,
+        annotations: true,
+        keep_annotations: false,
+        prefix: false,
+    };
+
+    /// Strict [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339#section-5.6) `date-time`,
+    /// e.g. `2021-10-17T02:03:01.123+10:00`.
+    ///
+    /// Only the extended format is accepted, seconds and a timezone are required,
+    /// and `T`/`Z` may be lowercase as allowed by the RFC.
+    pub const RFC3339: Self = ParseOptions {
+        lowercase: true,
+        space_separator: false,
+        underscore_separator: false,
+        basic_format: false,
+        ordinal_dates: false,
+        week_dates: false,
+        expanded_year_digits: 0,
+        plus_sign: false,
+        minus_sign: false,
+        unicode_minus: false,
+        date_only: false,
+        reduced_precision: false,
+        require_seconds: true,
+        end_of_day: false,
+        leap_seconds: false,
+        decimal_comma: false,
+        min_fraction_digits: 1,
+        max_fraction_digits: usize::MAX,
+        timezone: TimezoneRule::Required,
+        offset_hours_only: false,
+        offset_seconds: false,
+        utc_suffix: false,
+        check_digits: true,
+        annotations: false,
+        keep_annotations: false,
+        prefix: false,
+    };
+
+    /// [RFC 9557](https://www.rfc-editor.org/rfc/rfc9557) extended date-time, as sent by the JavaScript Temporal API,
+    /// e.g. `2021-10-17T02:03:01+02:00[Europe/Paris][u-ca=gregory]`.
+    ///
+    /// Like [`ParseOptions::RFC3339`], but followed by any bracketed annotations.
+    pub const RFC9557: Self = ParseOptions {
+        annotations: true,
+        ..ParseOptions::RFC3339
+    };
+
+    /// XML Schema [`xsd:dateTime`](https://www.w3.org/TR/xmlschema11-2/#dateTime),
+    /// e.g. `-2021-10-17T02:03:01.123Z`.
+    ///
+    /// Like [`ParseOptions::RFC3339`], but negative years and `24:00:00` are allowed, the timezone is optional,
+    /// and only uppercase `T`/`Z` are accepted.
+    pub const XSD: Self = ParseOptions {
+        lowercase: false,
+        minus_sign: true,
+        end_of_day: true,
+        timezone: TimezoneRule::Optional,
+        ..ParseOptions::RFC3339
+    };
+
+    /// HTML [local date and time string](https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#local-dates-and-times)
+    /// as used by `<input type="datetime-local">`, e.g. `2021-10-17T02:03`.
+    ///
+    /// Accepts `T` or space separators, optional seconds with up to 3 fractional digits, and no timezone.
+    pub const HTML_DATETIME_LOCAL: Self = ParseOptions {
+        lowercase: false,
+        space_separator: true,
+        require_seconds: false,
+        max_fraction_digits: 3,
+        timezone: TimezoneRule::Forbidden,
+        ..ParseOptions::RFC3339
+    };
+
+    /// Accept lowercase `t`, `z` and `utc` designators.
+    #[inline]
+    #[must_use]
+    pub const fn allow_lowercase(mut self, allow: bool) -> Self {
+        self.lowercase = allow;
+        self
+    }
+
+    /// Accept a space between the date and time, e.g. `2021-10-17 02:03:01`.
+    #[inline]
+    #[must_use]
+    pub const fn allow_space_separator(mut self, allow: bool) -> Self {
+        self.space_separator = allow;
+        self
+    }
+
+    /// Accept an underscore between the date and time, e.g. `2021-10-17_02:03:01`.
+    #[inline]
+    #[must_use]
+    pub const fn allow_underscore_separator(mut self, allow: bool) -> Self {
+        self.underscore_separator = allow;
+        self
+    }
+
+    /// Accept the basic format without `-` and `:` separators, e.g. `20211017T020301Z`.
+    ///
+    /// When disabled, all separators are required, including within the UTC offset.
+    #[inline]
+    #[must_use]
+    pub const fn allow_basic_format(mut self, allow: bool) -> Self {
+        self.basic_format = allow;
+        self
+    }
+
+    /// Accept ordinal dates, e.g. `2021-290` or `2021290`.
+    #[inline]
+    #[must_use]
+    pub const fn allow_ordinal_dates(mut self, allow: bool) -> Self {
+        self.ordinal_dates = allow;
+        self
+    }
+
+    /// Accept ISO week dates, e.g. `2021-W41-7` or `2021W417`.
+    #[inline]
+    #[must_use]
+    pub const fn allow_week_dates(mut self, allow: bool) -> Self {
+        self.week_dates = allow;
+        self
+    }
+
+    /// Accept ISO8601 expanded years, e.g. `+0020211017` or `-012021-10-17`, with the given
+    /// number of extra digits beyond the usual four. Expanded years must be signed,
+    /// while unsigned years are still parsed as four digits.
+    ///
+    /// The default of `0` disables expanded years.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is greater than 2, as `time` cannot represent years beyond ±999999.
+    /// In a `const` context, this is a compile-time error instead.
+    #[cfg(feature = "large-dates")]
+    #[inline]
+    #[must_use]
+    pub const fn expanded_year_digits(mut self, digits: u8) -> Self {
+        assert!(digits <= 2, "at most 2 extra year digits are supported");
+
+        self.expanded_year_digits = digits;
+        self
+    }
+
+    /// Accept a leading `+` sign on the year.
+    #[inline]
+    #[must_use]
+    pub const fn allow_plus_sign(mut self, allow: bool) -> Self {
+        self.plus_sign = allow;
+        self
+    }
+
+    /// Accept a leading `-` sign on the year, for years before 0000.
+    #[inline]
+    #[must_use]
+    pub const fn allow_minus_sign(mut self, allow: bool) -> Self {
+        self.minus_sign = allow;
+        self
+    }
+
+    /// Accept the Unicode MINUS SIGN (U+2212) wherever `-` is accepted as a sign.
+    #[inline]
+    #[must_use]
+    pub const fn allow_unicode_minus(mut self, allow: bool) -> Self {
+        self.unicode_minus = allow;
+        self
+    }
+
+    /// Accept a date without any time component, e.g. `2021-10-17`.
+    #[inline]
+    #[must_use]
+    pub const fn allow_date_only(mut self, allow: bool) -> Self {
+        self.date_only = allow;
+        self
+    }
+
+    /// Accept reduced-precision dates and times, e.g. `2021`, `2021-10`, `2021-W41` or `2021-10-17T02`,
+    /// and decimal fractions of hours or minutes, e.g. `2021-10-17T02.5` or `2021-10-17T02:03.25`.
+    ///
+    /// These expand to the start of the period they name, and the precision found is reported
+    /// by [`Timestamp::parse_with_precision`](crate::Timestamp::parse_with_precision).
+    #[inline]
+    #[must_use]
+    pub const fn allow_reduced_precision(mut self, allow: bool) -> Self {
+        self.reduced_precision = allow;
+        self
+    }
+
+    /// Require the seconds component to be present.
+    #[inline]
+    #[must_use]
+    pub const fn require_seconds(mut self, require: bool) -> Self {
+        self.require_seconds = require;
+        self
+    }
+
+    /// Accept `24:00`, with all other time components zero, as the end of the day,
+    /// e.g. `2021-10-17T24:00:00` is parsed as `2021-10-18T00:00:00`.
+    #[inline]
+    #[must_use]
+    pub const fn allow_end_of_day(mut self, allow: bool) -> Self {
+        self.end_of_day = allow;
+        self
+    }
+
+    /// Preserve leap seconds rather than clamping them to `59.999999999`.
+    ///
+    /// A second of `60` is parsed as second `59` with its fraction kept as-is, so it can be told apart
+    /// with [`Timestamp::parse_with_leap_second`](crate::Timestamp::parse_with_leap_second)
+    /// and written back out with [`Timestamp::format_leap_second_raw`](crate::Timestamp::format_leap_second_raw).
+    /// Seconds above `60` are always rejected, as is a second of `60` that does not end a UTC minute,
+    /// such as `12:30:60Z`, or `18:00:60+05:30`.
+    #[inline]
+    #[must_use]
+    pub const fn preserve_leap_seconds(mut self, preserve: bool) -> Self {
+        self.leap_seconds = preserve;
+        self
+    }
+
+    /// Accept `,` as the decimal separator for fractional seconds.
+    #[inline]
+    #[must_use]
+    pub const fn allow_decimal_comma(mut self, allow: bool) -> Self {
+        self.decimal_comma = allow;
+        self
+    }
+
+    /// Set the minimum and maximum number of fractional second digits when a decimal separator is present.
+    ///
+    /// Digits beyond nanosecond precision are still consumed but ignored.
+    #[inline]
+    #[must_use]
+    pub const fn fraction_digits(mut self, min: usize, max: usize) -> Self {
+        self.min_fraction_digits = min;
+        self.max_fraction_digits = max;
+        self
+    }
+
+    /// Set how the timezone designator is treated.
+    #[inline]
+    #[must_use]
+    pub const fn timezone(mut self, rule: TimezoneRule) -> Self {
+        self.timezone = rule;
+        self
+    }
+
+    /// Accept UTC offsets with only hours, e.g. `+05`.
+    #[inline]
+    #[must_use]
+    pub const fn allow_offset_hours_only(mut self, allow: bool) -> Self {
+        self.offset_hours_only = allow;
+        self
+    }
+
+    /// Accept UTC offsets with seconds, e.g. `+00:17:30`, as used for historical local mean time.
+    #[inline]
+    #[must_use]
+    pub const fn allow_offset_seconds(mut self, allow: bool) -> Self {
+        self.offset_seconds = allow;
+        self
+    }
+
+    /// Accept a trailing `UTC` in place of `Z`.
+    #[inline]
+    #[must_use]
+    pub const fn allow_utc_suffix(mut self, allow: bool) -> Self {
+        self.utc_suffix = allow;
+        self
+    }
+
+    /// Accept RFC 9557 bracketed annotations after the timestamp, e.g. `2021-10-17T02:03:01Z[Europe/Paris]`,
+    /// which can be read back with [`Timestamp::parse_with_annotations`](crate::Timestamp::parse_with_annotations).
+    ///
+    /// Annotations must follow the RFC 9557 grammar, and a critical numeric time zone must match the offset.
+    /// Critical annotations are rejected unless they are returned to the caller by
+    /// [`Timestamp::parse_with_annotations_and_options`](crate::Timestamp::parse_with_annotations_and_options),
+    /// as they must not be ignored.
+    #[inline]
+    #[must_use]
+    pub const fn allow_annotations(mut self, allow: bool) -> Self {
+        self.annotations = allow;
+        self
+    }
+
+    /// Reject non-numeric characters within numeric fields.
+    ///
+    /// This is always enabled when the `verify` Cargo feature is enabled.
+    #[inline]
+    #[must_use]
+    pub const fn check_digits(mut self, check: bool) -> Self {
+        self.check_digits = check;
+        self
+    }
+
+    /// Stop at the end of the timestamp rather than requiring it to fill the input.
+    ///
+    /// Digits are always checked, as without a fixed end there is nothing else to reject the
+    /// surrounding text, such as the `INFO` in `INFO 2021-10-17`.
+    #[inline(always)]
+    pub(crate) const fn prefix(mut self) -> Self {
+        self.prefix = true;
+        self.check_digits = true;
+        self
+    }
+
+    /// Accept critical annotations, as they will be returned to the caller to act upon.
+    #[inline(always)]
+    pub(crate) const fn keep_annotations(mut self) -> Self {
+        self.keep_annotations = true;
+        self
+    }
+}
@@ -1,4 +1,12 @@
-use time::{Date, Duration, Month, PrimitiveDateTime, Time};
+use time::{Date, Duration, Month, PrimitiveDateTime, Time, UtcOffset, Weekday};
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::options::{ParseOptions, TimezoneRule};
+use crate::parsed::{Parsed, ParsedOffset, Precision};
+
+pub mod batch;
+pub mod rfc2822;
+mod simd;
 
 /// Trait implemented locally for very fast parsing of small unsigned integers
 trait FastParse: Sized {
@@ -73,16 +81,173 @@ macro_rules! impl_fp {
 
 impl_fp!(u8, u16, u32);
 
+/// Branchless check that every byte is an ASCII digit, used when digits are
+/// not already verified by [`FastParse`].
+#[cfg(not(feature = "verify"))]
+#[inline(always)]
+fn is_digits(s: &[u8]) -> bool {
+    let mut valid = true;
+    for &c in s {
+        valid &= c.wrapping_sub(b'0') < 10;
+    }
+    valid
+}
+
+/// Locate the first non-digit within a chunk that failed to parse.
+#[cold]
+#[inline(never)]
+fn invalid_digit(chunk: &[u8], offset: usize, kind: ParseErrorKind) -> ParseError {
+    let pos = chunk.iter().position(|c| !c.is_ascii_digit()).unwrap_or(0);
+
+    ParseError::new(offset + pos, kind)
+}
+
+/// Error for a missing or unexpected character at the given offset.
+#[cold]
+#[inline(never)]
+fn unexpected(b: &[u8], offset: usize, kind: ParseErrorKind) -> ParseError {
+    match offset < b.len() {
+        true => ParseError::new(offset, kind),
+        false => ParseError::new(b.len(), ParseErrorKind::UnexpectedEnd),
+    }
+}
+
+/// A reduced-precision date expands to midnight at the start of the period it names.
+#[cold]
+#[inline(never)]
+fn reduced_date(
+    date: Result<Date, time::error::ComponentRange>,
+    pos: usize,
+    kind: ParseErrorKind,
+    precision: Precision,
+    len: usize,
+) -> Result<Parsed, ParseError> {
+    match date {
+        Ok(date) => Ok(Parsed::new(
+            PrimitiveDateTime::new(date, Time::MIDNIGHT),
+            ParsedOffset::Unspecified,
+            precision,
+            None,
+            len,
+        )),
+        Err(_) => Err(ParseError::new(pos, kind)),
+    }
+}
+
+/// Splits a decimal fraction of a unit of `unit_seconds`, in billionths, into minutes, seconds and nanoseconds.
+#[inline(always)]
+fn split_fraction(fraction: u32, unit_seconds: u64) -> (u8, u8, u32) {
+    let nanoseconds = fraction as u64 * unit_seconds;
+
+    (
+        (nanoseconds / 60_000_000_000) as u8,
+        (nanoseconds / 1_000_000_000 % 60) as u8,
+        (nanoseconds % 1_000_000_000) as u32,
+    )
+}
+
+// NOTE: Inlining this is cheaper than `Month::try_from(month).ok()`
+#[inline(always)]
+const fn to_month(month: u8) -> Option<Month> {
+    Some(match month {
+        1 => Month::January,
+        2 => Month::February,
+        3 => Month::March,
+        4 => Month::April,
+        5 => Month::May,
+        6 => Month::June,
+        7 => Month::July,
+        8 => Month::August,
+        9 => Month::September,
+        10 => Month::October,
+        11 => Month::November,
+        12 => Month::December,
+        _ => return None,
+    })
+}
+
+#[inline]
+pub fn parse_iso8601(b: &[u8]) -> Result<PrimitiveDateTime, ParseError> {
+    match parse_iso8601_with(b, &ParseOptions::LENIENT) {
+        Ok(parsed) => Ok(parsed.date_time),
+        Err(e) => Err(e),
+    }
+}
+
+/// Like [`parse_iso8601`], but falling back to the 6-digit expanded years written
+/// for years beyond ±9999, so that those can be read back.
 #[inline]
-pub fn parse_iso8601(b: &[u8]) -> Option<PrimitiveDateTime> {
+pub fn parse_iso8601_or_expanded(b: &[u8]) -> Result<PrimitiveDateTime, ParseError> {
+    let res = parse_iso8601(b);
+
+    #[cfg(feature = "large-dates")]
+    if res.is_err() && matches!(b.first(), Some(b'+' | b'-')) {
+        const EXPANDED: ParseOptions = ParseOptions::LENIENT.expanded_year_digits(2);
+
+        if let Ok(parsed) = parse_iso8601_with(b, &EXPANDED) {
+            return Ok(parsed.date_time);
+        }
+    }
+
+    res
+}
+
+#[inline(always)]
+pub fn parse_iso8601_with(b: &[u8], opts: &ParseOptions) -> Result<Parsed, ParseError> {
+    let parsed = match parse_timestamp(b, opts) {
+        Err(e) if opts.annotations && !opts.prefix => annotated(b, opts, e),
+        result => result,
+    }?;
+
+    // leap seconds are only ever inserted at the end of a UTC minute, whatever the local offset
+    if let (Some(pos), true) = (parsed.leap_second, opts.leap_seconds) {
+        if unlikely!(parsed.date_time.minute() != 59 || parsed.date_time.second() != 59) {
+            return Err(ParseError::new(pos, ParseErrorKind::SecondOutOfRange));
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Timestamps followed by RFC 9557 annotations fail the usual parse at the first `[`,
+/// so parse up to it again and check the annotations, keeping them off the hot path.
+#[cold]
+#[inline(never)]
+fn annotated(b: &[u8], opts: &ParseOptions, e: ParseError) -> Result<Parsed, ParseError> {
+    let Some(start) = b.iter().position(|&c| c == b'[') else {
+        return Err(e);
+    };
+
+    let mut parsed = parse_timestamp(&b[..start], opts)?;
+
+    crate::annotation::validate(b, start, parsed.offset, opts.keep_annotations)?;
+    parsed.len = b.len();
+
+    Ok(parsed)
+}
+
+#[inline(always)]
+fn parse_timestamp(b: &[u8], opts: &ParseOptions) -> Result<Parsed, ParseError> {
+    use ParseErrorKind as K;
+
     let (mut offset, negate) = match b.first().copied() {
         Some(c @ (b'+' | b'-' | 0xe2)) => {
+            let allowed = match c {
+                b'+' => opts.plus_sign,
+                b'-' => opts.minus_sign,
+                _ => opts.minus_sign && opts.unicode_minus,
+            };
+
+            if unlikely!(!allowed) {
+                return Err(ParseError::new(0, K::UnexpectedCharacter));
+            }
+
             let mut offset = 1;
 
             if unlikely!(c == 0xe2) {
                 // check for UTF8 Unicode MINUS SIGN
                 if unlikely!(b.get(offset..(offset + 2)) != Some(&[0x88u8, 0x92u8] as &[u8])) {
-                    return None;
+                    return Err(ParseError::new(0, K::UnexpectedCharacter));
                 }
 
                 offset += 2;
@@ -91,104 +256,341 @@ pub fn parse_iso8601(b: &[u8]) -> Option<PrimitiveDateTime> {
             (offset, (c != b'+') as i32)
         }
         Some(_) => (0, 0),
-        None => return None,
+        None => return Err(ParseError::new(0, K::Empty)),
+    };
+
+    // whether the timestamp may end at the given offset, which when parsing a prefix
+    // is any byte that cannot continue the timestamp
+    let at_boundary = |offset: usize| match b.get(offset) {
+        None => true,
+        Some(c) => {
+            opts.prefix
+                && !matches!(
+                    c,
+                    b'0'..=b'9' | b'-' | b':' | b'.' | b',' | b'W' | b'w' | b'T' | b't'
+                )
+        }
     };
 
     macro_rules! parse {
-        ($len:expr, $ty:ty $(, $eat_byte:expr)?) => {loop {
+        ($len:expr, $ty:ty $(, $eat_byte:expr)? $(; $kind:ident)?) => {loop {
+            #[allow(unused_variables)]
+            let kind = K::InvalidDigit;
+            $(let kind = K::$kind;)?
+
             if let Some(chunk) = b.get(offset..(offset + $len)) {
                 if let Some(res) = <$ty as FastParse>::parse(chunk) {
+                    #[cfg(not(feature = "verify"))]
+                    if opts.check_digits && unlikely!(!is_digits(chunk)) {
+                        return Err(invalid_digit(chunk, offset, kind));
+                    }
+
                     offset += $len;
 
                     $(
                         // conditional increment is slightly faster than branchless
                         if let Some($eat_byte) = b.get(offset) {
                             offset += 1;
+                        } else if !opts.basic_format && !(opts.reduced_precision && at_boundary(offset)) {
+                            return Err(unexpected(b, offset, match kind {
+                                K::InvalidDigit => K::UnexpectedCharacter,
+                                kind => kind,
+                            }));
                         }
                     )?
 
                     break res;
                 }
+
+                return Err(invalid_digit(chunk, offset, kind));
             }
 
-            return None;
+            return Err(ParseError::new(b.len(), K::UnexpectedEnd));
         }};
     }
 
-    // NOTE: converting u16 to i16 is fine since it's less than 9999
-    let mut year = parse!(4, u16, b'-') as i32; // YYYY-?
+    // parses a decimal fraction such as `.123` or `,5` into billionths,
+    // along with the number of digits (saturating at 255)
+    macro_rules! fraction {
+        () => {{
+            if unlikely!(b[offset] == b',' && !opts.decimal_comma) {
+                return Err(ParseError::new(offset, K::UnexpectedCharacter));
+            }
 
-    // branchless conditional negation seems faster for i16
-    // done immediately after parsing to avoid keeping the negate register
-    year = (year ^ -negate) + negate;
+            offset += 1;
 
-    let month = parse!(2, u8, b'-'); // MM-?
-    let day = parse!(2, u8); // DD
+            let fraction_pos = offset;
+            let mut fraction = 0;
+            let mut factor: u32 = 100_000_000; // up to 9 decimal places
 
-    // NOTE: Inlining this is cheaper than `Month::try_from(month).ok()?`
-    let month = match month {
-        1 => Month::January,
-        2 => Month::February,
-        3 => Month::March,
-        4 => Month::April,
-        5 => Month::May,
-        6 => Month::June,
-        7 => Month::July,
-        8 => Month::August,
-        9 => Month::September,
-        10 => Month::October,
-        11 => Month::November,
-        12 => Month::December,
-        _ => return None,
-    };
+            // NOTE: After 9 decimal places, this does nothing other than consume digits,
+            // as factor will be zero, so fraction will not change
+            while let Some(&c) = b.get(offset) {
+                let d = c.wrapping_sub(b'0');
 
-    #[cfg(feature = "verify")]
-    unsafe {
-        assume!(-9999 <= year && year <= 9999);
+                if d > 9 {
+                    break; // break on non-numeric input
+                }
+
+                fraction += d as u32 * factor;
+                factor /= 10;
+                offset += 1;
+            }
+
+            let digits = offset - fraction_pos;
+
+            if unlikely!(digits < opts.min_fraction_digits || digits > opts.max_fraction_digits) {
+                return Err(ParseError::new(fraction_pos, K::InvalidFraction));
+            }
+
+            (fraction, if digits > 255 { 255 } else { digits as u8 })
+        }};
     }
 
-    let Ok(date) = Date::from_calendar_date(year, month, day) else {
-        return None;
+    // reduced precision forms end right after a numeric field
+    let reduced_end =
+        |offset: usize| opts.reduced_precision && at_boundary(offset) && b[offset - 1].is_ascii_digit();
+
+    // the most common layout, `YYYY-MM-DDTHH:MM:SS`, is loaded and checked in one go
+    let header = match offset {
+        0 => simd::parse_header(b),
+        _ => None,
+    };
+
+    let date = 'date: {
+        if let Some(h) = header {
+            offset = 10; // YYYY-MM-DD
+
+            let Some(month) = to_month(h.month) else {
+                return Err(ParseError::new(5, K::MonthOutOfRange));
+            };
+
+            match Date::from_calendar_date(h.year as i32, month, h.day) {
+                Ok(date) => break 'date date,
+                Err(_) => return Err(ParseError::new(8, K::InvalidDayForMonth)),
+            }
+        }
+
+        // NOTE: expanded years require a sign, and have an agreed-upon number of extra digits
+        #[cfg(feature = "large-dates")]
+        let mut year = match offset != 0 && opts.expanded_year_digits > 0 {
+            true => parse!(4 + opts.expanded_year_digits as usize, u32, b'-') as i32, // ±YYYYYY-?
+            false => parse!(4, u16, b'-') as i32,
+        };
+
+        // NOTE: converting u16 to i16 is fine since it's less than 9999
+        #[cfg(not(feature = "large-dates"))]
+        let mut year = parse!(4, u16, b'-') as i32; // YYYY-?
+
+        // branchless conditional negation seems faster for i16
+        // done immediately after parsing to avoid keeping the negate register
+        year = (year ^ -negate) + negate;
+
+        // SAFETY: verified input can only contain 4 digits and a sign
+        #[cfg(all(feature = "verify", not(feature = "large-dates")))]
+        unsafe {
+            assume!((-9999..=9999).contains(&year));
+        }
+
+        if unlikely!(reduced_end(offset)) {
+            return reduced_date(
+                Date::from_calendar_date(year, Month::January, 1),
+                0,
+                K::OutOfRange,
+                Precision::Year,
+                offset,
+            );
+        }
+
+        let is_week = opts.week_dates
+            && match b.get(offset) {
+                Some(b'W') => true,
+                Some(b'w') => opts.lowercase,
+                _ => false,
+            };
+
+        // NOTE: ordinal dates have exactly 3 digits for the day of the year, whereas calendar
+        // dates are either `MM-DD` or `MMDD`, so the third and fourth bytes tell them apart
+        let is_ordinal = !is_week
+            && opts.ordinal_dates
+            && matches!(b.get(offset + 2), Some(b'0'..=b'9'))
+            && !matches!(b.get(offset + 3), Some(b'0'..=b'9'));
+
+        if unlikely!(is_week) {
+            offset += 1; // W
+
+            let week_pos = offset;
+            let week = parse!(2, u8, b'-'); // ww-?
+
+            if unlikely!(reduced_end(offset)) {
+                let date = Date::from_iso_week_date(year, week, Weekday::Monday);
+                return reduced_date(date, week_pos, K::WeekOutOfRange, Precision::Week, offset);
+            }
+
+            let weekday_pos = offset;
+
+            let weekday = match parse!(1, u8) {
+                1 => Weekday::Monday,
+                2 => Weekday::Tuesday,
+                3 => Weekday::Wednesday,
+                4 => Weekday::Thursday,
+                5 => Weekday::Friday,
+                6 => Weekday::Saturday,
+                7 => Weekday::Sunday,
+                _ => return Err(ParseError::new(weekday_pos, K::WeekdayOutOfRange)),
+            };
+
+            // NOTE: the year parsed here is the ISO week-numbering year, which is
+            // converted to the calendar year if the week spans a year boundary
+            match Date::from_iso_week_date(year, week, weekday) {
+                Ok(date) => date,
+                Err(_) => return Err(ParseError::new(week_pos, K::WeekOutOfRange)),
+            }
+        } else if unlikely!(is_ordinal) {
+            let ordinal_pos = offset;
+            let ordinal = parse!(3, u16); // DDD
+
+            match Date::from_ordinal_date(year, ordinal) {
+                Ok(date) => date,
+                Err(_) => return Err(ParseError::new(ordinal_pos, K::OrdinalOutOfRange)),
+            }
+        } else {
+            let month_pos = offset;
+            let month = parse!(2, u8, b'-'); // MM-?
+            let day_pos = offset;
+
+            let Some(month) = to_month(month) else {
+                return Err(ParseError::new(month_pos, K::MonthOutOfRange));
+            };
+
+            // NOTE: `YYYYMM` is not allowed, as it could be confused with `YYMMDD`
+            if unlikely!(reduced_end(offset) && b[month_pos - 1] == b'-') {
+                let date = Date::from_calendar_date(year, month, 1);
+                return reduced_date(date, month_pos, K::MonthOutOfRange, Precision::Month, offset);
+            }
+
+            let day = parse!(2, u8); // DD
+
+            match Date::from_calendar_date(year, month, day) {
+                Ok(date) => date,
+                Err(_) => return Err(ParseError::new(day_pos, K::InvalidDayForMonth)),
+            }
+        }
     };
 
     let mut date_time = PrimitiveDateTime::new(date, Time::MIDNIGHT);
 
+    // when parsing a prefix, a separator that isn't followed by a time is left unconsumed
+    let time_follows = !opts.prefix || matches!(b.get(offset + 1), Some(b'0'..=b'9'));
+
     match b.get(offset) {
-        Some(b'T' | b't' | b' ' | b'_') => {
+        Some(b'T') if time_follows => {
             offset += 1; // T
         }
+        Some(b't') if opts.lowercase && time_follows => offset += 1,
+        Some(b' ') if opts.space_separator && time_follows => offset += 1,
+        Some(b'_') if opts.underscore_separator && time_follows => offset += 1,
+
         // date-only, None means it's at the end of the string
-        None => return Some(date_time),
-        _ => return None,
+        None if opts.date_only => {
+            return Ok(Parsed::new(
+                date_time,
+                ParsedOffset::Unspecified,
+                Precision::Day,
+                None,
+                offset,
+            ));
+        }
+        Some(_) if opts.date_only && opts.prefix => {
+            return Ok(Parsed::new(
+                date_time,
+                ParsedOffset::Unspecified,
+                Precision::Day,
+                None,
+                offset,
+            ));
+        }
+        _ => return Err(unexpected(b, offset, K::UnexpectedCharacter)),
     }
 
-    let hour = parse!(2, u8, b':'); // HH:?
-    let minute = parse!(2, u8, b':'); // mm:?
+    let hour_pos = offset;
+    let (hour, mut minute_pos, mut second_pos);
 
+    let mut minute = 0;
     let mut second = 0;
     let mut nanosecond = 0;
+    let mut precision = Precision::Hour;
+    let mut leap_second = None;
+    let mut has_seconds = false;
+
+    // hours or minutes may be the smallest unit, possibly with a decimal fraction
+    let reduced_time = opts.reduced_precision && !opts.require_seconds;
+
+    'time: {
+        if let Some(h) = header {
+            (hour, minute, second) = (h.hour, h.minute, h.second);
+            (minute_pos, second_pos, offset) = (14, 17, simd::HEADER_LEN); // YYYY-MM-DDTHH:MM:SS
+            precision = Precision::Second;
+            has_seconds = true;
+            break 'time;
+        }
 
-    if let Some(b'0'..=b'9') = b.get(offset) {
-        second = parse!(2, u8);
-
-        if let Some(b'.' | b',') = b.get(offset) {
-            offset += 1;
+        hour = parse!(2, u8); // HH
+        minute_pos = offset;
+        second_pos = offset;
+
+        match b.get(offset) {
+            Some(b':') => offset += 1,
+            Some(b'0'..=b'9') if opts.basic_format => {}
+            Some(b'.' | b',') if reduced_time => {
+                let (fraction, digits) = fraction!();
+                (minute, second, nanosecond) = split_fraction(fraction, 60 * 60);
+                precision = Precision::FractionalHour(digits);
+                break 'time;
+            }
+            _ if reduced_time => break 'time,
+            _ => return Err(unexpected(b, offset, K::UnexpectedCharacter)),
+        }
 
-            let mut factor: u32 = 100_000_000; // up to 9 decimal places
+        minute_pos = offset;
+        minute = parse!(2, u8); // mm
+        second_pos = offset;
+        precision = Precision::Minute;
+
+        match b.get(offset) {
+            Some(b'.' | b',') if reduced_time => {
+                let (fraction, digits) = fraction!();
+                (_, second, nanosecond) = split_fraction(fraction, 60);
+                precision = Precision::FractionalMinute(digits);
+                break 'time;
+            }
+            // conditional increment is slightly faster than branchless
+            Some(b':') => {
+                offset += 1;
+                second_pos += 1;
+            }
+            _ => {}
+        }
 
-            // NOTE: After 9 decimal places, this does nothing other than consume digits,
-            // as factor will be zero, so nanosecond will not change
-            while let Some(&c) = b.get(offset) {
-                let d = c.wrapping_sub(b'0');
+        if let Some(b'0'..=b'9') = b.get(offset) {
+            // extended format requires the `:` before seconds
+            if unlikely!(!opts.basic_format && second_pos == minute_pos + 2) {
+                return Err(ParseError::new(offset, K::UnexpectedCharacter));
+            }
 
-                if d > 9 {
-                    break; // break on non-numeric input
-                }
+            second = parse!(2, u8);
+            precision = Precision::Second;
+            has_seconds = true;
+        } else if opts.require_seconds {
+            return Err(unexpected(b, offset, K::UnexpectedCharacter));
+        }
+    }
 
-                nanosecond += d as u32 * factor;
-                factor /= 10;
-                offset += 1;
-            }
+    if has_seconds {
+        if let Some(b'.' | b',') = b.get(offset) {
+            let (fraction, digits) = fraction!();
+            nanosecond = fraction;
+            precision = Precision::FractionalSecond(digits);
         }
 
         // if leap seconds, ignore the parsed value and set it to just before 60
@@ -196,13 +598,24 @@ pub fn parse_iso8601(b: &[u8]) -> Option<PrimitiveDateTime> {
         // NOTE: This will also "fix" malformed seconds input
         if unlikely!(second > 59) {
             // but don't neglect invalid input if necessary
+            #[cfg(not(feature = "verify"))]
+            let check = opts.check_digits || opts.leap_seconds;
             #[cfg(feature = "verify")]
-            if unlikely!(second > 60) {
-                return None;
+            let check = true;
+
+            if check && unlikely!(second > 60) {
+                return Err(ParseError::new(second_pos, K::SecondOutOfRange));
             }
 
+            if second == 60 {
+                leap_second = Some(second_pos);
+            }
             second = 59;
-            nanosecond = 999_999_999;
+
+            // when preserving leap seconds, the fraction of the leap second is kept as-is
+            if !opts.leap_seconds {
+                nanosecond = 999_999_999;
+            }
         }
     }
 
@@ -221,70 +634,211 @@ pub fn parse_iso8601(b: &[u8]) -> Option<PrimitiveDateTime> {
 
     date_time = match Time::from_hms_nano(hour, minute, second, nanosecond) {
         Ok(time) => date_time.replace_time(time),
-        _ => return None,
+
+        // `24:00:00` is the end of the day, which is midnight at the start of the next day
+        Err(_) if hour == 24 && opts.end_of_day && (minute | second) == 0 && nanosecond == 0 => {
+            match date.next_day() {
+                Some(date) => PrimitiveDateTime::new(date, Time::MIDNIGHT),
+                None => return Err(ParseError::new(hour_pos, K::OutOfRange)),
+            }
+        }
+
+        Err(e) => {
+            return Err(match e.name() {
+                "hour" => ParseError::new(hour_pos, K::HourOutOfRange),
+                "minute" => ParseError::new(minute_pos, K::MinuteOutOfRange),
+                _ => ParseError::new(second_pos, K::SecondOutOfRange),
+            })
+        }
     };
 
     let tz = b.get(offset).copied();
 
+    if unlikely!(tz.is_some() && opts.timezone == TimezoneRule::Forbidden) {
+        if opts.prefix {
+            let parsed = Parsed::new(
+                date_time,
+                ParsedOffset::Unspecified,
+                precision,
+                leap_second,
+                offset,
+            );
+            return Ok(parsed);
+        }
+
+        return Err(ParseError::new(offset, K::UnexpectedCharacter));
+    }
+
     offset += 1;
 
+    // a complete timestamp must fill the input, unless parsing a prefix
+    let at_end = |offset: usize| opts.prefix || offset == b.len();
+
     match tz {
         // Z
-        Some(b'Z' | b'z') if likely!(offset == b.len()) => Some(date_time),
+        Some(b'Z') if likely!(at_end(offset)) => Ok(Parsed::new(
+            date_time,
+            ParsedOffset::Utc,
+            precision,
+            leap_second,
+            offset,
+        )),
+        Some(b'z') if opts.lowercase && at_end(offset) => Ok(Parsed::new(
+            date_time,
+            ParsedOffset::Utc,
+            precision,
+            leap_second,
+            offset,
+        )),
 
         // timezone, like +00:00
         Some(c @ (b'+' | b'-' | 0xe2)) => {
+            let tz_pos = offset - 1;
+
             if unlikely!(c == 0xe2) {
                 // check for UTF8 Unicode MINUS SIGN
-                if unlikely!(b.get(offset..(offset + 2)) != Some(&[0x88u8, 0x92u8] as &[u8])) {
-                    return None;
+                if unlikely!(
+                    !opts.unicode_minus || b.get(offset..(offset + 2)) != Some(&[0x88u8, 0x92u8] as &[u8])
+                ) {
+                    return Err(ParseError::new(tz_pos, K::UnexpectedCharacter));
                 }
                 offset += 2;
             }
 
-            let tz_offset_hour = parse!(2, u8, b':') as i64;
-            let tz_offset_minute = parse!(2, u8) as i64;
+            let tz_offset_hour = parse!(2, u8; InvalidOffset) as i64;
+            let mut tz_offset_minute = 0;
+            let mut tz_offset_second = 0;
+
+            match b.get(offset) {
+                // ±hh:mm or ±hh:mm:ss
+                Some(b':') => {
+                    offset += 1;
+                    tz_offset_minute = parse!(2, u8; InvalidOffset) as i64;
 
-            if unlikely!(offset != b.len()) {
-                return None;
+                    if let (Some(b':'), true) = (b.get(offset), opts.offset_seconds) {
+                        offset += 1;
+                        tz_offset_second = parse!(2, u8; InvalidOffset) as i64;
+                    }
+                }
+                // ±hhmm or ±hhmmss
+                Some(b'0'..=b'9') if opts.basic_format => {
+                    tz_offset_minute = parse!(2, u8; InvalidOffset) as i64;
+
+                    if let (Some(b'0'..=b'9'), true) = (b.get(offset), opts.offset_seconds) {
+                        tz_offset_second = parse!(2, u8; InvalidOffset) as i64;
+                    }
+                }
+                // ±hh
+                _ if opts.offset_hours_only && at_boundary(offset) => {}
+                _ => return Err(unexpected(b, offset, K::InvalidOffset)),
             }
 
-            if tz_offset_hour == 0 && tz_offset_minute == 0 {
-                return Some(date_time);
+            if unlikely!(tz_offset_hour > 23 || tz_offset_minute > 59 || tz_offset_second > 59) {
+                return Err(ParseError::new(tz_pos, K::InvalidOffset));
             }
 
-            let tz_offset = Duration::seconds(60 * 60 * tz_offset_hour + tz_offset_minute * 60);
+            if unlikely!(!at_end(offset)) {
+                return Err(ParseError::new(offset, K::TrailingCharacters));
+            }
+
+            let negative = c != b'+';
+
+            if tz_offset_hour == 0 && tz_offset_minute == 0 && tz_offset_second == 0 {
+                return Ok(Parsed::new(
+                    date_time,
+                    match negative {
+                        true => ParsedOffset::UnknownLocal,
+                        false => ParsedOffset::Fixed(UtcOffset::UTC),
+                    },
+                    precision,
+                    leap_second,
+                    offset,
+                ));
+            }
+
+            let (h, m, s) = (
+                tz_offset_hour as i8,
+                tz_offset_minute as i8,
+                tz_offset_second as i8,
+            );
+            let utc_offset = match negative {
+                true => UtcOffset::from_hms(-h, -m, -s),
+                false => UtcOffset::from_hms(h, m, s),
+            };
+
+            let Ok(utc_offset) = utc_offset else {
+                return Err(ParseError::new(tz_pos, K::InvalidOffset));
+            };
+
+            let tz_offset =
+                Duration::seconds(60 * 60 * tz_offset_hour + tz_offset_minute * 60 + tz_offset_second);
 
             // these generate function calls regardless, so avoid
             // negating the offset and just chose which call to make
-            let checked_op: fn(PrimitiveDateTime, Duration) -> Option<PrimitiveDateTime> = match c != b'+' {
+            let checked_op: fn(PrimitiveDateTime, Duration) -> Option<PrimitiveDateTime> = match negative {
                 true => PrimitiveDateTime::checked_add as _,
                 false => PrimitiveDateTime::checked_sub as _,
             };
 
-            checked_op(date_time, tz_offset)
+            match checked_op(date_time, tz_offset) {
+                Some(date_time) => Ok(Parsed::new(
+                    date_time,
+                    ParsedOffset::Fixed(utc_offset),
+                    precision,
+                    leap_second,
+                    offset,
+                )),
+                None => Err(ParseError::new(tz_pos, K::OutOfRange)),
+            }
         }
 
         // Parse trailing "UTC", but it does nothing, same as Z
-        Some(b'U' | b'u') => match b.get(offset..(offset + 2)) {
-            None => None,
-            Some(tc) => {
+        Some(b'U' | b'u') if opts.utc_suffix => match b.get((offset - 1)..(offset + 2)) {
+            None => Err(ParseError::new(b.len(), K::UnexpectedEnd)),
+            Some(utc) => {
                 // avoid multiple branches when this loop is unrolled
                 let mut invalid = false;
-                for (c, r) in tc.iter().zip(b"tc") {
+                for (c, r) in utc.iter().zip(b"utc") {
                     invalid |= (*c | 0x20) != *r;
                 }
 
-                if unlikely!(invalid || (offset + 2) != b.len()) {
-                    return None;
+                if unlikely!(invalid || (!opts.lowercase && utc != b"UTC")) {
+                    return Err(ParseError::new(offset - 1, K::UnexpectedCharacter));
+                }
+
+                if unlikely!(!at_end(offset + 2)) {
+                    return Err(ParseError::new(offset + 2, K::TrailingCharacters));
                 }
 
-                Some(date_time)
+                Ok(Parsed::new(
+                    date_time,
+                    ParsedOffset::Utc,
+                    precision,
+                    leap_second,
+                    offset + 2,
+                ))
             }
         },
-        None => Some(date_time),
-
-        _ => None,
+        None if opts.timezone != TimezoneRule::Required => Ok(Parsed::new(
+            date_time,
+            ParsedOffset::Unspecified,
+            precision,
+            leap_second,
+            offset - 1,
+        )),
+        None => Err(ParseError::new(b.len(), K::UnexpectedEnd)),
+
+        // the timestamp ends without a timezone, leaving the remaining input unconsumed
+        Some(_) if opts.prefix && opts.timezone != TimezoneRule::Required => Ok(Parsed::new(
+            date_time,
+            ParsedOffset::Unspecified,
+            precision,
+            leap_second,
+            offset - 1,
+        )),
+
+        Some(b'Z' | b'z') if offset != b.len() => Err(ParseError::new(offset, K::TrailingCharacters)),
+        _ => Err(ParseError::new(offset - 1, K::UnexpectedCharacter)),
     }
 }
 
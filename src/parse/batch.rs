@@ -0,0 +1,201 @@
+//! Parsing whole columns of timestamps, where consecutive values very often share
+//! the same layout, like `2021-10-17T02:03:01.123Z`, and differ only in their digits.
+//!
+//! The first value of a run is parsed normally, then any following values with identical
+//! separators, fraction length and suffix are parsed by the fixed-layout fast path,
+//! several at a time where the vectors are wide enough. Anything the fast path cannot
+//! handle falls back to the general parser, so results always match [`parse_iso8601`].
+
+use time::{Date, Duration, PrimitiveDateTime, Time};
+
+use super::{parse_iso8601, simd, to_month};
+use crate::Timestamp;
+
+/// The layout shared by a run of values, taken from the first value of the run.
+struct Layout<'a> {
+    len: usize,
+    /// Date/time separator
+    separator: u8,
+    /// Fraction separator and number of digits, or zero digits if none
+    fraction: (u8, usize),
+    /// Anything after the seconds and fraction, such as `Z` or `+10:00`
+    suffix: &'a [u8],
+    /// The last 8 bytes and a mask of those within the suffix, to compare short suffixes at once
+    tail: (u64, u64),
+    /// Local time minus UTC, implied by the suffix
+    shift: Duration,
+    /// The most recently parsed date, as sorted columns repeat it often
+    last_date: ([u16; 3], Date),
+}
+
+impl<'a> Layout<'a> {
+    /// Starts a run with a value already parsed by the general parser, if it has the fixed layout.
+    fn new(b: &'a [u8], utc: PrimitiveDateTime) -> Option<Self> {
+        if b.len() < simd::HEADER_LEN || b[4] != b'-' || b[7] != b'-' || b[13] != b':' || b[16] != b':' {
+            return None;
+        }
+
+        let mut fraction = (0, 0);
+        let mut suffix = simd::HEADER_LEN;
+
+        if let Some(&sep @ (b'.' | b',')) = b.get(simd::HEADER_LEN) {
+            let digits = b[simd::HEADER_LEN + 1..]
+                .iter()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+
+            if digits == 0 || digits > 9 {
+                return None;
+            }
+
+            fraction = (sep, digits);
+            suffix += 1 + digits;
+        }
+
+        let mut layout = Layout {
+            len: b.len(),
+            separator: b[10],
+            fraction,
+            suffix: &b[suffix..],
+            tail: (
+                simd::read_u64(b, b.len() - 8),
+                match b.len() - suffix {
+                    0 => 0,
+                    n @ 1..=7 => !0 << (8 * (8 - n)),
+                    _ => !0,
+                },
+            ),
+            shift: Duration::ZERO,
+            last_date: ([0; 3], Date::MIN),
+        };
+
+        // the offset is the same for every value in the run, so find it from the first
+        let local = layout.local(b, simd::pairs(b)?)?;
+        layout.shift = local - utc;
+
+        Some(layout)
+    }
+
+    /// Checks everything but the digits, which are checked while parsing.
+    #[inline(always)]
+    fn matches(&self, b: &[u8]) -> bool {
+        let (sep, digits) = self.fraction;
+
+        b.len() == self.len
+            && b[4] == b'-'
+            && b[7] == b'-'
+            && b[10] == self.separator
+            && b[13] == b':'
+            && b[16] == b':'
+            && (digits == 0 || b[simd::HEADER_LEN] == sep)
+            && (simd::read_u64(b, self.len - 8) ^ self.tail.0) & self.tail.1 == 0
+            && (self.suffix.len() <= 8 || b[self.len - self.suffix.len()..] == *self.suffix)
+    }
+
+    /// Builds the local date and time from a value matching the layout, or `None`
+    /// for anything out of range, such as leap seconds, which the general parser handles.
+    #[inline(always)]
+    fn local(&mut self, b: &[u8], pairs: [u16; 8]) -> Option<PrimitiveDateTime> {
+        let [century, year, month, day, hour, minute, second, _] = pairs;
+
+        let key = [century * 100 + year, month, day];
+
+        if key != self.last_date.0 {
+            let month = to_month(month as u8)?;
+            self.last_date = (
+                key,
+                Date::from_calendar_date(key[0] as i32, month, day as u8).ok()?,
+            );
+        }
+
+        let nanosecond = match self.fraction.1 {
+            0 => 0,
+            digits => simd::fraction(b, digits)?,
+        };
+
+        let time = Time::from_hms_nano(hour as u8, minute as u8, second as u8, nanosecond).ok()?;
+
+        Some(PrimitiveDateTime::new(self.last_date.1, time))
+    }
+
+    /// Finishes a value matching the layout, falling back to the general parser if needed.
+    #[inline(always)]
+    fn finish(&mut self, b: &[u8], pairs: Option<[u16; 8]>) -> Option<Timestamp> {
+        let local = match pairs {
+            Some(pairs) => self.local(b, pairs),
+            None => None,
+        };
+
+        let utc = match local {
+            Some(local) if self.shift.is_zero() => Some(local),
+            Some(local) => local.checked_sub(self.shift),
+            None => None,
+        };
+
+        match utc {
+            Some(utc) => Some(Timestamp(utc)),
+            None => fallback(b),
+        }
+    }
+}
+
+/// Values within a run that the fast path cannot handle, such as leap seconds or invalid digits.
+#[cold]
+#[inline(never)]
+fn fallback(b: &[u8]) -> Option<Timestamp> {
+    parse_iso8601(b).ok().map(Timestamp)
+}
+
+/// Parses `out.len()` values, where `input(i)` gives the bytes of the `i`th value,
+/// or `None` if it is missing.
+#[inline(always)]
+pub fn parse_batch<'a>(input: impl Fn(usize) -> Option<&'a [u8]>, out: &mut [Option<Timestamp>]) {
+    let mut layout: Option<Layout<'a>> = None;
+    let mut i = 0;
+
+    while i < out.len() {
+        let a = input(i);
+
+        if let (Some(run), Some(a)) = (&mut layout, a) {
+            if run.matches(a) {
+                let b = match i + 1 < out.len() {
+                    true => input(i + 1).filter(|b| run.matches(b)),
+                    false => None,
+                };
+
+                match b {
+                    Some(b) => {
+                        let [pa, pb] = simd::pairs_x2(a, b);
+                        out[i] = run.finish(a, pa);
+                        out[i + 1] = run.finish(b, pb);
+                        i += 2;
+                    }
+                    None => {
+                        out[i] = run.finish(a, simd::pairs(a));
+                        i += 1;
+                    }
+                }
+
+                continue;
+            }
+        }
+
+        // outside of any run, parse normally and try to start a new one,
+        // keeping the current run if this value doesn't fit the fast path
+        out[i] = match a {
+            Some(a) => match parse_iso8601(a) {
+                Ok(utc) => {
+                    if let Some(new) = Layout::new(a, utc) {
+                        layout = Some(new);
+                    }
+
+                    Some(Timestamp(utc))
+                }
+                Err(_) => None,
+            },
+            None => None,
+        };
+
+        i += 1;
+    }
+}
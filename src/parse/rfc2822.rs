@@ -0,0 +1,334 @@
+//! Parsing of the date formats used by email and HTTP headers: RFC 2822 with numeric or named zones,
+//! and the IMF-fixdate, RFC 850 and asctime formats accepted by RFC 7231 for HTTP-dates.
+//!
+//! ```text
+//! Sun, 17 Oct 2021 02:03:01 +1000      RFC 2822
+//! Sun, 17 Oct 2021 02:03:01 GMT        IMF-fixdate
+//! Sunday, 17-Oct-21 02:03:01 GMT       RFC 850
+//! Sun Oct 17 02:03:01 2021             asctime
+//! ```
+
+use time::{Date, Duration, Month, PrimitiveDateTime, Time, Weekday};
+
+use super::unexpected;
+use crate::error::{ParseError, ParseErrorKind as K};
+
+#[rustfmt::skip]
+const WEEKDAYS: [(&[u8], Weekday); 7] = [
+    (b"monday", Weekday::Monday), (b"tuesday", Weekday::Tuesday), (b"wednesday", Weekday::Wednesday),
+    (b"thursday", Weekday::Thursday), (b"friday", Weekday::Friday), (b"saturday", Weekday::Saturday),
+    (b"sunday", Weekday::Sunday),
+];
+
+#[rustfmt::skip]
+const MONTHS: [(&[u8], Month); 12] = [
+    (b"jan", Month::January), (b"feb", Month::February), (b"mar", Month::March), (b"apr", Month::April),
+    (b"may", Month::May), (b"jun", Month::June), (b"jul", Month::July), (b"aug", Month::August),
+    (b"sep", Month::September), (b"oct", Month::October), (b"nov", Month::November), (b"dec", Month::December),
+];
+
+/// Named zones from RFC 2822, as hours east of UTC
+#[rustfmt::skip]
+const ZONES: [(&[u8], i64); 12] = [
+    (b"ut", 0), (b"utc", 0), (b"gmt", 0),
+    (b"est", -5), (b"edt", -4), (b"cst", -6), (b"cdt", -5),
+    (b"mst", -7), (b"mdt", -6), (b"pst", -8), (b"pdt", -7),
+    (b"z", 0),
+];
+
+struct Cursor<'a> {
+    b: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.b.get(self.pos).copied()
+    }
+
+    #[inline]
+    fn eat(&mut self, c: u8) -> bool {
+        let found = self.peek() == Some(c);
+        self.pos += found as usize;
+        found
+    }
+
+    /// Skips folding whitespace, returning whether there was any
+    #[inline]
+    fn skip_ws(&mut self) -> bool {
+        let start = self.pos;
+        while let Some(b' ' | b'\t' | b'\r' | b'\n') = self.peek() {
+            self.pos += 1;
+        }
+        self.pos != start
+    }
+
+    #[inline]
+    fn require_ws(&mut self) -> Result<(), ParseError> {
+        match self.skip_ws() {
+            true => Ok(()),
+            false => Err(unexpected(self.b, self.pos, K::UnexpectedCharacter)),
+        }
+    }
+
+    #[inline]
+    fn expect(&mut self, c: u8) -> Result<(), ParseError> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(unexpected(self.b, self.pos, K::UnexpectedCharacter)),
+        }
+    }
+
+    /// Consumes a run of ASCII letters
+    #[inline]
+    fn word(&mut self) -> &'a [u8] {
+        let start = self.pos;
+        while let Some(b'a'..=b'z' | b'A'..=b'Z') = self.peek() {
+            self.pos += 1;
+        }
+        &self.b[start..self.pos]
+    }
+
+    /// Consumes between `min` and `max` digits, returning the value and number of digits
+    #[inline]
+    fn digits(&mut self, min: usize, max: usize) -> Result<(u32, usize), ParseError> {
+        let start = self.pos;
+        let mut value = 0;
+
+        while let Some(c @ b'0'..=b'9') = self.peek() {
+            if self.pos - start == max {
+                return Err(ParseError::new(self.pos, K::UnexpectedCharacter));
+            }
+
+            value = value * 10 + (c - b'0') as u32;
+            self.pos += 1;
+        }
+
+        match self.pos - start {
+            n if n < min => Err(unexpected(self.b, self.pos, K::InvalidDigit)),
+            n => Ok((value, n)),
+        }
+    }
+
+    /// Consumes a name from the given table, ignoring case
+    #[inline]
+    fn name<T: Copy>(&mut self, table: &[(&[u8], T)], kind: K) -> Result<T, ParseError> {
+        let start = self.pos;
+        let word = self.word();
+
+        match table.iter().find(|(name, _)| word.eq_ignore_ascii_case(name)) {
+            Some((_, value)) => Ok(*value),
+            None => Err(unexpected(self.b, start, kind)),
+        }
+    }
+
+    /// Consumes a weekday, either abbreviated or in full
+    #[inline]
+    fn weekday(&mut self) -> Result<Weekday, ParseError> {
+        let start = self.pos;
+        let word = self.word();
+
+        let weekday = WEEKDAYS.iter().find(|(name, _)| {
+            word.eq_ignore_ascii_case(name) || (word.len() == 3 && word.eq_ignore_ascii_case(&name[..3]))
+        });
+
+        match weekday {
+            Some((_, weekday)) => Ok(*weekday),
+            None => Err(unexpected(self.b, start, K::UnexpectedCharacter)),
+        }
+    }
+
+    /// `hh:mm[:ss]`, with leap seconds treated as the last nanosecond before them
+    fn time(&mut self, require_seconds: bool) -> Result<Time, ParseError> {
+        let hour_pos = self.pos;
+        let (hour, _) = self.digits(2, 2)?;
+        self.expect(b':')?;
+        let minute_pos = self.pos;
+        let (minute, _) = self.digits(2, 2)?;
+
+        let second_pos = self.pos + 1;
+        let mut second = 0;
+        if require_seconds || self.peek() == Some(b':') {
+            self.expect(b':')?;
+            (second, _) = self.digits(2, 2)?;
+        }
+
+        let mut nanosecond = 0;
+        if second == 60 {
+            second = 59;
+            nanosecond = 999_999_999;
+        }
+
+        Time::from_hms_nano(hour as u8, minute as u8, second as u8, nanosecond).map_err(|e| match e.name() {
+            "hour" => ParseError::new(hour_pos, K::HourOutOfRange),
+            "minute" => ParseError::new(minute_pos, K::MinuteOutOfRange),
+            _ => ParseError::new(second_pos, K::SecondOutOfRange),
+        })
+    }
+
+    /// `+hhmm`, `-hhmm` or a named zone, as seconds east of UTC
+    fn zone(&mut self) -> Result<i64, ParseError> {
+        let zone_pos = self.pos;
+
+        match self.peek() {
+            Some(sign @ (b'+' | b'-')) => {
+                self.pos += 1;
+
+                let (hhmm, _) = self
+                    .digits(4, 4)
+                    .map_err(|_| unexpected(self.b, zone_pos, K::InvalidOffset))?;
+                let (hours, minutes) = ((hhmm / 100) as i64, (hhmm % 100) as i64);
+
+                if minutes > 59 {
+                    return Err(ParseError::new(zone_pos, K::InvalidOffset));
+                }
+
+                let seconds = (hours * 60 + minutes) * 60;
+
+                Ok(if sign == b'-' { -seconds } else { seconds })
+            }
+            Some(b'a'..=b'z' | b'A'..=b'Z') => {
+                let word = self.word();
+
+                if let Some((_, hours)) = ZONES.iter().find(|(name, _)| word.eq_ignore_ascii_case(name)) {
+                    return Ok(hours * 60 * 60);
+                }
+
+                // military zones were defined with the wrong signs, so RFC 2822 treats them as UTC
+                match word {
+                    [c] if !c.eq_ignore_ascii_case(&b'j') => Ok(0),
+                    _ => Err(ParseError::new(zone_pos, K::InvalidOffset)),
+                }
+            }
+            _ => Err(unexpected(self.b, zone_pos, K::InvalidOffset)),
+        }
+    }
+
+    /// Skips trailing whitespace and comments, such as `(UTC)`, then requires the end of input
+    fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_ws();
+
+        while self.eat(b'(') {
+            let mut depth = 1;
+
+            while depth > 0 {
+                match self.peek() {
+                    Some(b'(') => depth += 1,
+                    Some(b')') => depth -= 1,
+                    Some(b'\\') => self.pos += 1,
+                    Some(_) => {}
+                    None => return Err(ParseError::new(self.b.len(), K::UnexpectedEnd)),
+                }
+                self.pos += 1;
+            }
+
+            self.skip_ws();
+        }
+
+        match self.pos == self.b.len() {
+            true => Ok(()),
+            false => Err(ParseError::new(self.pos, K::TrailingCharacters)),
+        }
+    }
+}
+
+/// Parse any of the formats above to a UTC timestamp.
+pub fn parse_rfc2822(b: &[u8]) -> Result<PrimitiveDateTime, ParseError> {
+    let mut c = Cursor { b, pos: 0 };
+
+    c.skip_ws();
+
+    let mut weekday = None;
+
+    match c.peek() {
+        None => return Err(ParseError::new(0, K::Empty)),
+        Some(b'a'..=b'z' | b'A'..=b'Z') => {
+            weekday = Some((c.pos, c.weekday()?));
+            c.skip_ws();
+
+            // asctime has no comma after the weekday
+            if !c.eat(b',') {
+                return asctime(c, weekday);
+            }
+
+            c.skip_ws();
+        }
+        _ => {}
+    }
+
+    let day_pos = c.pos;
+    let (day, _) = c.digits(1, 2)?;
+
+    // RFC 850 separates the date with dashes
+    let rfc850 = c.eat(b'-');
+    if !rfc850 {
+        c.require_ws()?;
+    }
+
+    let month = c.name(&MONTHS, K::MonthOutOfRange)?;
+
+    match rfc850 {
+        true => c.expect(b'-')?,
+        false => c.require_ws()?,
+    }
+
+    let (year, year_digits) = c.digits(2, 4)?;
+
+    // two and three-digit years from obsolete formats, as interpreted by RFC 2822
+    let year = match year_digits {
+        2 if year < 50 => 2000 + year,
+        2 | 3 => 1900 + year,
+        _ => year,
+    };
+
+    c.require_ws()?;
+    let time = c.time(false)?;
+    c.require_ws()?;
+    let zone = c.zone()?;
+    c.end()?;
+
+    finish(b, year, month, day, day_pos, time, zone, weekday)
+}
+
+/// `Sun Oct 17 02:03:01 2021`, where the day may be padded with a space instead of a zero
+fn asctime(mut c: Cursor, weekday: Option<(usize, Weekday)>) -> Result<PrimitiveDateTime, ParseError> {
+    let month = c.name(&MONTHS, K::MonthOutOfRange)?;
+    c.require_ws()?;
+    let day_pos = c.pos;
+    let (day, _) = c.digits(1, 2)?;
+    c.require_ws()?;
+    let time = c.time(true)?;
+    c.require_ws()?;
+    let (year, _) = c.digits(4, 4)?;
+    c.end()?;
+
+    finish(c.b, year, month, day, day_pos, time, 0, weekday)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn finish(
+    b: &[u8],
+    year: u32,
+    month: Month,
+    day: u32,
+    day_pos: usize,
+    time: Time,
+    zone: i64,
+    weekday: Option<(usize, Weekday)>,
+) -> Result<PrimitiveDateTime, ParseError> {
+    let Ok(date) = Date::from_calendar_date(year as i32, month, day as u8) else {
+        return Err(ParseError::new(day_pos, K::InvalidDayForMonth));
+    };
+
+    if let Some((weekday_pos, weekday)) = weekday {
+        if date.weekday() != weekday {
+            return Err(ParseError::new(weekday_pos, K::WeekdayMismatch));
+        }
+    }
+
+    match PrimitiveDateTime::new(date, time).checked_sub(Duration::seconds(zone)) {
+        Some(date_time) => Ok(date_time),
+        None => Err(ParseError::new(b.len(), K::OutOfRange)),
+    }
+}
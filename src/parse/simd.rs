@@ -0,0 +1,423 @@
+//! Vectorized parsing of the fixed-layout start of common timestamps, `YYYY-MM-DD?HH:MM:SS`,
+//! which loads all 19 bytes at once and checks every digit before converting them.
+//!
+//! Paths are selected by the `target_feature`s enabled at compile time, without runtime detection,
+//! so the SSE4.1 and AVX2 paths need e.g. `-C target-cpu=native` on x86, while NEON is baseline on aarch64.
+
+/// Date and time fields from the start of an extended-format calendar timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Header {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+/// Length of `YYYY-MM-DDTHH:MM:SS`
+pub(super) const HEADER_LEN: usize = 19;
+
+#[cfg(target_feature = "sse4.1")]
+import_intrinsics!(x86::{__m128i});
+
+// positions of the digits within `YYYY-MM-DDTHH:MM:SS`, gathered into 7 pairs of `YY YY MM DD HH mm SS`,
+// where the seconds are taken from a second load starting at byte 3, so both loads are in-bounds
+#[cfg(any(
+    target_feature = "sse4.1",
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
+const GATHER_LO: [i8; 16] = [0, 1, 2, 3, 5, 6, 8, 9, 11, 12, 14, 15, -1, -1, -1, -1];
+#[cfg(any(
+    target_feature = "sse4.1",
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
+const GATHER_HI: [i8; 16] = [-1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, 14, 15, -1, -1];
+
+// the last pair is unused, so fill it with zeroes to pass validation
+#[cfg(any(
+    target_feature = "sse4.1",
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
+const ZERO_FILL: [u8; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, b'0', b'0'];
+
+/// Parses the fixed-layout `YYYY-MM-DD?HH:MM:SS` start of an extended-format timestamp,
+/// leaving the date/time separator at byte 10 for the caller to check.
+///
+/// Returns `None` for any other layout or if any digit is invalid,
+/// so the general parser can handle it or report the error.
+#[inline(always)]
+pub(super) fn parse_header(b: &[u8]) -> Option<Header> {
+    if b.len() < HEADER_LEN || b[4] != b'-' || b[7] != b'-' || b[13] != b':' || b[16] != b':' {
+        return None;
+    }
+
+    let [century, year, month, day, hour, minute, second, _] = pairs(b)?;
+
+    Some(Header {
+        year: century * 100 + year,
+        month: month as u8,
+        day: day as u8,
+        hour: hour as u8,
+        minute: minute as u8,
+        second: second as u8,
+    })
+}
+
+/// Gathers the digits of `YYYY-MM-DD?HH:MM:SS` into 7 pairs of `YY YY MM DD HH mm SS`,
+/// or `None` if any is not a digit. The input must be at least [`HEADER_LEN`] bytes.
+#[inline(always)]
+#[allow(unreachable_code)]
+pub(super) fn pairs(b: &[u8]) -> Option<[u16; 8]> {
+    #[cfg(target_feature = "avx2")]
+    // SAFETY: Checked for AVX2 support, and the caller checked the length
+    return unsafe { pairs_avx2(b) };
+
+    #[cfg(target_feature = "sse4.1")]
+    // SAFETY: Checked for SSE4.1 support, and the caller checked the length
+    return unsafe { pairs_sse41(b) };
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    // SAFETY: Checked for NEON support, and the caller checked the length
+    return unsafe { pairs_neon(b) };
+
+    pairs_swar(b)
+}
+
+/// Like [`pairs`], but for two inputs at once where the vectors are wide enough.
+#[inline(always)]
+#[allow(unreachable_code)]
+pub(super) fn pairs_x2(a: &[u8], b: &[u8]) -> [Option<[u16; 8]>; 2] {
+    #[cfg(target_feature = "avx2")]
+    // SAFETY: Checked for AVX2 support, and the caller checked the lengths
+    return unsafe { pairs_x2_avx2(a, b) };
+
+    [pairs(a), pairs(b)]
+}
+
+/// Converts gathered ASCII digits into pairs, or `None` if any is not a digit.
+#[inline(always)]
+#[cfg(target_feature = "sse4.1")]
+unsafe fn digits_to_pairs_sse41(digits: __m128i) -> Option<[u16; 8]> {
+    import_intrinsics!(x86::{
+        _mm_set1_epi8, _mm_set1_epi16, _mm_sub_epi8,
+        _mm_subs_epu8, _mm_testz_si128, _mm_maddubs_epi16
+    });
+
+    let digits = _mm_sub_epi8(digits, _mm_set1_epi8(b'0' as i8));
+
+    // saturating subtraction leaves non-zero bytes for anything that wrapped past 9
+    let invalid = _mm_subs_epu8(digits, _mm_set1_epi8(9));
+
+    if _mm_testz_si128(invalid, invalid) == 0 {
+        return None;
+    }
+
+    // multiply-add adjacent digits as `10 * a + b`
+    let pairs = _mm_maddubs_epi16(digits, _mm_set1_epi16(0x010A));
+
+    Some(core::mem::transmute::<__m128i, [u16; 8]>(pairs))
+}
+
+#[inline(always)]
+#[cfg(target_feature = "sse4.1")]
+unsafe fn pairs_sse41(b: &[u8]) -> Option<[u16; 8]> {
+    import_intrinsics!(x86::{_mm_loadu_si128, _mm_or_si128, _mm_shuffle_epi8});
+
+    let lo = _mm_loadu_si128(b.as_ptr() as *const __m128i); // YYYY-MM-DDTHH:MM
+    let hi = _mm_loadu_si128(b.as_ptr().add(3) as *const __m128i); // Y-MM-DDTHH:MM:SS
+
+    let gather_lo = core::mem::transmute::<[i8; 16], __m128i>(GATHER_LO);
+    let gather_hi = core::mem::transmute::<[i8; 16], __m128i>(GATHER_HI);
+    let zero_fill = core::mem::transmute::<[u8; 16], __m128i>(ZERO_FILL);
+
+    let digits = _mm_or_si128(
+        _mm_or_si128(_mm_shuffle_epi8(lo, gather_lo), _mm_shuffle_epi8(hi, gather_hi)),
+        zero_fill,
+    );
+
+    digits_to_pairs_sse41(digits)
+}
+
+#[inline(always)]
+#[cfg(target_feature = "avx2")]
+unsafe fn pairs_avx2(b: &[u8]) -> Option<[u16; 8]> {
+    import_intrinsics!(x86::{
+        __m256i, _mm_loadu_si128, _mm_or_si128, _mm256_castsi128_si256,
+        _mm256_inserti128_si256, _mm256_shuffle_epi8, _mm256_castsi256_si128, _mm256_extracti128_si256
+    });
+
+    let lo = _mm_loadu_si128(b.as_ptr() as *const __m128i); // YYYY-MM-DDTHH:MM
+    let hi = _mm_loadu_si128(b.as_ptr().add(3) as *const __m128i); // Y-MM-DDTHH:MM:SS
+
+    // gather both halves with a single shuffle, then merge them
+    let both = _mm256_inserti128_si256::<1>(_mm256_castsi128_si256(lo), hi);
+    let gather = core::mem::transmute::<[[i8; 16]; 2], __m256i>([GATHER_LO, GATHER_HI]);
+    let gathered = _mm256_shuffle_epi8(both, gather);
+
+    let zero_fill = core::mem::transmute::<[u8; 16], __m128i>(ZERO_FILL);
+
+    let digits = _mm_or_si128(
+        _mm_or_si128(
+            _mm256_castsi256_si128(gathered),
+            _mm256_extracti128_si256::<1>(gathered),
+        ),
+        zero_fill,
+    );
+
+    digits_to_pairs_sse41(digits)
+}
+
+/// Each 128-bit lane handles one input, with the same gather as [`pairs_sse41`].
+#[inline(always)]
+#[cfg(target_feature = "avx2")]
+unsafe fn pairs_x2_avx2(a: &[u8], b: &[u8]) -> [Option<[u16; 8]>; 2] {
+    import_intrinsics!(x86::{
+        __m256i, _mm_loadu_si128, _mm256_castsi128_si256, _mm256_inserti128_si256, _mm256_shuffle_epi8,
+        _mm256_or_si256, _mm256_set1_epi8, _mm256_set1_epi16, _mm256_sub_epi8, _mm256_subs_epu8,
+        _mm256_cmpeq_epi8, _mm256_setzero_si256, _mm256_movemask_epi8, _mm256_maddubs_epi16
+    });
+
+    let load = |b: &[u8], at: usize| _mm_loadu_si128(b.as_ptr().add(at) as *const __m128i);
+
+    let lo = _mm256_inserti128_si256::<1>(_mm256_castsi128_si256(load(a, 0)), load(b, 0));
+    let hi = _mm256_inserti128_si256::<1>(_mm256_castsi128_si256(load(a, 3)), load(b, 3));
+
+    let gather_lo = core::mem::transmute::<[[i8; 16]; 2], __m256i>([GATHER_LO, GATHER_LO]);
+    let gather_hi = core::mem::transmute::<[[i8; 16]; 2], __m256i>([GATHER_HI, GATHER_HI]);
+    let zero_fill = core::mem::transmute::<[[u8; 16]; 2], __m256i>([ZERO_FILL, ZERO_FILL]);
+
+    let digits = _mm256_or_si256(
+        _mm256_or_si256(
+            _mm256_shuffle_epi8(lo, gather_lo),
+            _mm256_shuffle_epi8(hi, gather_hi),
+        ),
+        zero_fill,
+    );
+
+    let digits = _mm256_sub_epi8(digits, _mm256_set1_epi8(b'0' as i8));
+
+    // one bit per valid byte, 16 for each input
+    let invalid = _mm256_subs_epu8(digits, _mm256_set1_epi8(9));
+    let valid = _mm256_movemask_epi8(_mm256_cmpeq_epi8(invalid, _mm256_setzero_si256())) as u32;
+
+    let pairs = _mm256_maddubs_epi16(digits, _mm256_set1_epi16(0x010A));
+    let [pa, pb] = core::mem::transmute::<__m256i, [[u16; 8]; 2]>(pairs);
+
+    [
+        (valid & 0xFFFF == 0xFFFF).then_some(pa),
+        (valid >> 16 == 0xFFFF).then_some(pb),
+    ]
+}
+
+#[inline(always)]
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+unsafe fn pairs_neon(b: &[u8]) -> Option<[u16; 8]> {
+    use core::arch::aarch64::{
+        vdupq_n_u8, vld1q_u8, vmaxvq_u8, vmulq_u8, vorrq_u8, vpaddlq_u8, vqtbl1q_u8, vst1q_u16, vsubq_u8,
+    };
+
+    const WEIGHTS: [u8; 16] = [10, 1, 10, 1, 10, 1, 10, 1, 10, 1, 10, 1, 10, 1, 10, 1];
+
+    let lo = vld1q_u8(b.as_ptr()); // YYYY-MM-DDTHH:MM
+    let hi = vld1q_u8(b.as_ptr().add(3)); // Y-MM-DDTHH:MM:SS
+
+    // out-of-range indices (-1 as 255) select zero
+    let gather_lo = vld1q_u8(GATHER_LO.as_ptr() as *const u8);
+    let gather_hi = vld1q_u8(GATHER_HI.as_ptr() as *const u8);
+
+    let digits = vorrq_u8(
+        vorrq_u8(vqtbl1q_u8(lo, gather_lo), vqtbl1q_u8(hi, gather_hi)),
+        vld1q_u8(ZERO_FILL.as_ptr()),
+    );
+
+    let digits = vsubq_u8(digits, vdupq_n_u8(b'0'));
+
+    if vmaxvq_u8(digits) > 9 {
+        return None;
+    }
+
+    // `10 * a + b` for each adjacent pair
+    let pairs = vpaddlq_u8(vmulq_u8(digits, vld1q_u8(WEIGHTS.as_ptr())));
+
+    let mut out = [0u16; 8];
+    vst1q_u16(out.as_mut_ptr(), pairs);
+    Some(out)
+}
+
+const ZEROS: u64 = 0x3030_3030_3030_3030;
+const HIGH: u64 = 0x8080_8080_8080_8080;
+
+#[inline(always)]
+pub(super) fn read_u64(b: &[u8], at: usize) -> u64 {
+    let mut buf = [0; 8];
+    buf.copy_from_slice(&b[at..at + 8]);
+    u64::from_le_bytes(buf)
+}
+
+/// Replaces bytes outside of `mask` with '0', then checks each byte is within '0'..='9', as any byte below '0'
+/// sets the high bit when subtracting, and any byte above '9' sets the high bit when adding 0x46.
+///
+/// Returns the digit values, one per byte.
+#[inline(always)]
+fn swar_digits(chunk: u64, mask: u64) -> Option<u64> {
+    let x = (chunk & mask) | (ZEROS & !mask);
+
+    if ((x.wrapping_sub(ZEROS) | x.wrapping_add(0x4646_4646_4646_4646)) & HIGH) != 0 {
+        return None;
+    }
+
+    Some(x - ZEROS)
+}
+
+/// Portable fallback, checking 8 bytes at a time.
+#[cfg_attr(
+    any(
+        target_feature = "sse4.1",
+        all(target_arch = "aarch64", target_feature = "neon")
+    ),
+    allow(dead_code)
+)]
+#[inline(always)]
+fn pairs_swar(b: &[u8]) -> Option<[u16; 8]> {
+    let digits = |chunk, mask| swar_digits(chunk, mask).map(u64::to_le_bytes);
+
+    let d0 = digits(read_u64(b, 0), 0x00FF_FF00_FFFF_FFFF)?; // YYYY-MM-
+    let d1 = digits(read_u64(b, 8), 0xFFFF_00FF_FF00_FFFF)?; // DDTHH:MM
+    let d2 = digits(read_u64(b, 11), 0xFFFF_0000_0000_0000)?; // HH:MM:SS
+
+    let pair = |d: [u8; 8], i: usize| d[i] as u16 * 10 + d[i + 1] as u16;
+
+    Some([
+        pair(d0, 0),
+        pair(d0, 2),
+        pair(d0, 5),
+        pair(d1, 0),
+        pair(d1, 3),
+        pair(d1, 6),
+        pair(d2, 6),
+        0,
+    ])
+}
+
+/// Parses the 1 to 9 fractional digits following `YYYY-MM-DD?HH:MM:SS.` as nanoseconds,
+/// 8 bytes at a time, or `None` if any is not a digit.
+#[inline(always)]
+pub(super) fn fraction(b: &[u8], digits: usize) -> Option<u32> {
+    const SCALE: [u32; 9] = [
+        1,
+        10,
+        100,
+        1000,
+        10_000,
+        100_000,
+        1_000_000,
+        10_000_000,
+        100_000_000,
+    ];
+
+    let end = HEADER_LEN + 1 + digits;
+
+    // only the 9th digit doesn't fit in the last 8 bytes
+    let (lead, rest) = match digits {
+        9 => match b[HEADER_LEN + 1].wrapping_sub(b'0') {
+            lead @ 0..=9 => (lead as u32, 8),
+            _ => return None,
+        },
+        _ => (0, digits),
+    };
+
+    // the digits end the chunk, so the leading bytes are replaced with zeroes
+    let v = swar_digits(read_u64(b, end - 8), !0 << (8 * (8 - rest)))?;
+
+    // combine adjacent digits, then pairs, then quads, with the first digit as the most significant
+    let v = (v.wrapping_mul(10 << 8 | 1) >> 8) & 0x00FF_00FF_00FF_00FF;
+    let v = (v.wrapping_mul(100 << 16 | 1) >> 16) & 0x0000_FFFF_0000_FFFF;
+    let v = v.wrapping_mul(10_000 << 32 | 1) >> 32;
+
+    Some((lead * 100_000_000 + v as u32) * SCALE[9 - digits])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_header() {
+        let header = Header {
+            year: 2021,
+            month: 10,
+            day: 17,
+            hour: 2,
+            minute: 3,
+            second: 1,
+        };
+
+        assert_eq!(parse_header(b"2021-10-17T02:03:01"), Some(header));
+        assert_eq!(parse_header(b"2021-10-17 02:03:01.123456789+10:00"), Some(header));
+        assert_eq!(parse_header(b"2021-10-17T02:03"), None);
+        assert_eq!(parse_header(b"20211017T020301Z...."), None);
+
+        // every digit position is checked
+        for pos in [0, 1, 2, 3, 5, 6, 8, 9, 11, 12, 14, 15, 17, 18] {
+            for c in [b'/', b':', b'a', 0xFF] {
+                let mut input = *b"2021-10-17T02:03:01";
+                input[pos] = c;
+
+                assert_eq!(pairs_swar(&input), None, "{pos} {c}");
+                assert_eq!(pairs(&input), None, "{pos} {c}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_pairs() {
+        let mut input = *b"0000-00-00T00:00:00";
+
+        for i in 0..=9999u16 {
+            let value = i as u32;
+            let digits =
+                [value / 1000, value / 100 % 10, value / 10 % 10, value % 10].map(|d| d as u8 | b'0');
+
+            input[..4].copy_from_slice(&digits);
+            input[17..].copy_from_slice(&digits[2..]);
+            input[14..16].copy_from_slice(&digits[..2]);
+
+            let expected = [i / 100, i % 100, 0, 0, 0, i / 100, i % 100, 0];
+
+            assert_eq!(pairs_swar(&input), Some(expected));
+            assert_eq!(pairs(&input), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_fraction() {
+        for digits in 1..=9 {
+            let mut input = *b"2021-10-17T02:03:01.123456789Z";
+
+            let nanos = &b"123456789"[..digits];
+            let expected =
+                core::str::from_utf8(nanos).unwrap().parse::<u32>().unwrap() * 10u32.pow(9 - digits as u32);
+
+            assert_eq!(fraction(&input, digits), Some(expected), "{digits}");
+
+            input[HEADER_LEN + digits] = b'x';
+            assert_eq!(fraction(&input, digits), None, "{digits}");
+        }
+    }
+
+    #[test]
+    fn test_pairs_x2() {
+        let valid = *b"2021-10-17T02:03:01";
+        let mut invalid = valid;
+        invalid[18] = b'x';
+
+        let expected = pairs(&valid);
+        assert!(expected.is_some());
+
+        assert_eq!(pairs_x2(&valid, &valid), [expected, expected]);
+        assert_eq!(pairs_x2(&valid, &invalid), [expected, None]);
+        assert_eq!(pairs_x2(&invalid, &valid), [None, expected]);
+        assert_eq!(pairs_x2(&invalid, &invalid), [None, None]);
+    }
+}
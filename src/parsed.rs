@@ -0,0 +1,85 @@
+use time::{PrimitiveDateTime, UtcOffset};
+
+/// The timezone designator found at the end of a parsed timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParsedOffset {
+    /// No timezone was present, so UTC was assumed.
+    Unspecified,
+    /// `Z` or a trailing `UTC`.
+    Utc,
+    /// RFC 3339's "unknown local offset", `-00:00`.
+    UnknownLocal,
+    /// A numeric offset, such as `+10:00`, including `+00:00`.
+    Fixed(UtcOffset),
+}
+
+impl ParsedOffset {
+    /// The offset from UTC that was applied, which is zero for all but [`ParsedOffset::Fixed`].
+    #[inline]
+    #[must_use]
+    pub const fn utc_offset(self) -> UtcOffset {
+        match self {
+            ParsedOffset::Fixed(offset) => offset,
+            _ => UtcOffset::UTC,
+        }
+    }
+}
+
+/// The smallest unit present in a parsed timestamp.
+///
+/// Reduced-precision timestamps, such as `2021-10` or `2021-10-17T02`, expand to the start of the
+/// period they name, so this is needed to tell `2021-10` apart from `2021-10-01T00:00:00`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Precision {
+    /// `2021`
+    Year,
+    /// `2021-10`
+    Month,
+    /// `2021-W41`
+    Week,
+    /// `2021-10-17`, `2021-290` or `2021-W41-7`
+    Day,
+    /// `2021-10-17T02`
+    Hour,
+    /// `2021-10-17T02.5`, with the given number of decimal digits
+    FractionalHour(u8),
+    /// `2021-10-17T02:03`
+    Minute,
+    /// `2021-10-17T02:03.25`, with the given number of decimal digits
+    FractionalMinute(u8),
+    /// `2021-10-17T02:03:01`
+    Second,
+    /// `2021-10-17T02:03:01.123`, with the given number of decimal digits
+    FractionalSecond(u8),
+}
+
+/// Everything recovered by the parser, before it is narrowed down to what the caller needs.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Parsed {
+    pub date_time: PrimitiveDateTime,
+    pub offset: ParsedOffset,
+    pub precision: Precision,
+    /// Position of a second of `60`, if any
+    pub leap_second: Option<usize>,
+    /// Number of bytes consumed, which is the whole input unless parsing a prefix
+    pub len: usize,
+}
+
+impl Parsed {
+    #[inline(always)]
+    pub const fn new(
+        date_time: PrimitiveDateTime,
+        offset: ParsedOffset,
+        precision: Precision,
+        leap_second: Option<usize>,
+        len: usize,
+    ) -> Self {
+        Parsed {
+            date_time,
+            offset,
+            precision,
+            leap_second,
+            len,
+        }
+    }
+}
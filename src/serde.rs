@@ -0,0 +1,539 @@
+//! Alternate serde encodings for a single field, for use with `#[serde(with = "...")]`.
+//!
+//! By default, a [`Timestamp`] is serialized as an ISO8601 string in human-readable formats and as
+//! integer milliseconds in binary formats. These modules pick one encoding for both:
+//!
+//! ```rust
+//! # use iso8601_timestamp::Timestamp;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Claims {
+//!     #[serde(with = "iso8601_timestamp::serde::unix_seconds")]
+//!     exp: Timestamp,
+//!     #[serde(with = "iso8601_timestamp::serde::option_string", default)]
+//!     revoked: Option<Timestamp>,
+//! }
+//!
+//! let claims: Claims = serde_json::from_str(r#"{"exp": 1634436181, "revoked": ""}"#).unwrap();
+//!
+//! assert_eq!(claims.exp, Timestamp::parse("2021-10-17T02:03:01Z").unwrap());
+//! assert_eq!(claims.revoked, None);
+//! assert_eq!(serde_json::to_string(&claims).unwrap(), r#"{"exp":1634436181,"revoked":""}"#);
+//! ```
+//!
+//! Integer encodings truncate any finer precision toward zero, as the default encoding does.
+
+use core::convert::TryFrom;
+use core::fmt;
+
+use serde_core::de::{Deserializer, Error, SeqAccess, Visitor};
+use serde_core::ser::{Error as _, Serializer};
+
+use crate::Timestamp;
+
+const OUT_OF_RANGE: &str = "Timestamp out of range";
+
+/// Converts to a whole number of units since the Unix Epoch, truncated toward zero.
+#[inline]
+pub(crate) fn to_units(ts: &Timestamp, per_second: i64) -> Option<i64> {
+    let nanoseconds = ts.duration_since(Timestamp::UNIX_EPOCH).whole_nanoseconds();
+    i64::try_from(nanoseconds / (1_000_000_000 / per_second) as i128).ok()
+}
+
+/// Converts from a whole number of units since the Unix Epoch.
+#[inline]
+pub(crate) fn from_units(units: i64, per_second: i64) -> Option<Timestamp> {
+    let nanoseconds = units.rem_euclid(per_second) * (1_000_000_000 / per_second);
+
+    Timestamp::UNIX_EPOCH.checked_add(time::Duration::new(
+        units.div_euclid(per_second),
+        nanoseconds as i32,
+    ))
+}
+
+/// Splits into whole seconds since the Unix Epoch and the nanoseconds within that second.
+#[inline]
+pub(crate) fn to_parts(ts: &Timestamp) -> (i64, u32) {
+    let duration = ts.duration_since(Timestamp::UNIX_EPOCH);
+    let (seconds, nanoseconds) = (duration.whole_seconds(), duration.subsec_nanoseconds());
+
+    match nanoseconds < 0 {
+        true => (seconds - 1, (nanoseconds + 1_000_000_000) as u32),
+        false => (seconds, nanoseconds as u32),
+    }
+}
+
+/// Joins whole seconds since the Unix Epoch and the nanoseconds within that second.
+#[inline]
+pub(crate) fn from_parts(seconds: i64, nanoseconds: u32) -> Option<Timestamp> {
+    if nanoseconds >= 1_000_000_000 {
+        return None;
+    }
+
+    Timestamp::UNIX_EPOCH.checked_add(time::Duration::new(seconds, nanoseconds as i32))
+}
+
+/// Reads `(seconds, nanoseconds)` from a sequence, as written by [`lossless`].
+#[inline]
+pub(crate) fn visit_parts<'de, A: SeqAccess<'de>>(mut seq: A) -> Result<Timestamp, A::Error> {
+    let Some(seconds) = seq.next_element::<i64>()? else {
+        return Err(A::Error::invalid_length(0, &"seconds and nanoseconds"));
+    };
+
+    let Some(nanoseconds) = seq.next_element::<u32>()? else {
+        return Err(A::Error::invalid_length(1, &"seconds and nanoseconds"));
+    };
+
+    from_parts(seconds, nanoseconds).ok_or_else(|| A::Error::custom(OUT_OF_RANGE))
+}
+
+#[inline]
+fn serialize_units<S: Serializer>(ts: &Timestamp, per_second: i64, serializer: S) -> Result<S::Ok, S::Error> {
+    match to_units(ts, per_second) {
+        Some(units) => serializer.serialize_i64(units),
+        None => Err(S::Error::custom(OUT_OF_RANGE)),
+    }
+}
+
+#[inline]
+fn deserialize_units<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    per_second: i64,
+) -> Result<Timestamp, D::Error> {
+    struct UnitsVisitor(i64);
+
+    impl Visitor<'_> for UnitsVisitor {
+        type Value = Timestamp;
+
+        #[inline]
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an integer Unix timestamp")
+        }
+
+        #[inline]
+        fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+            from_units(v, self.0).ok_or_else(|| E::custom(OUT_OF_RANGE))
+        }
+
+        #[inline]
+        fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+            match i64::try_from(v) {
+                Ok(v) => self.visit_i64(v),
+                Err(_) => Err(E::custom(OUT_OF_RANGE)),
+            }
+        }
+    }
+
+    deserializer.deserialize_i64(UnitsVisitor(per_second))
+}
+
+macro_rules! unix_module {
+    ($(#[$meta:meta])* $name:ident, $per_second:expr) => {
+        $(#[$meta])*
+        pub mod $name {
+            use serde_core::de::Deserializer;
+            use serde_core::ser::Serializer;
+
+            use crate::Timestamp;
+
+            /// Serializes the timestamp as an `i64`.
+            #[inline]
+            pub fn serialize<S: Serializer>(ts: &Timestamp, serializer: S) -> Result<S::Ok, S::Error> {
+                super::serialize_units(ts, $per_second, serializer)
+            }
+
+            /// Deserializes the timestamp from an integer.
+            #[inline]
+            pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+                super::deserialize_units(deserializer, $per_second)
+            }
+        }
+    };
+}
+
+unix_module! {
+    /// Unix timestamp in whole seconds, as used by JWT claims.
+    unix_seconds, 1
+}
+
+unix_module! {
+    /// Unix timestamp in milliseconds, as used by JavaScript's `Date.now()`.
+    unix_millis, 1_000
+}
+
+unix_module! {
+    /// Unix timestamp in microseconds.
+    unix_micros, 1_000_000
+}
+
+unix_module! {
+    /// Unix timestamp in nanoseconds, which can only represent years 1677 to 2262.
+    unix_nanos, 1_000_000_000
+}
+
+/// Unix timestamp in seconds as an `f64`, which keeps about microsecond precision for present-day timestamps,
+/// so is rounded to the nearest microsecond when deserialized.
+pub mod float_seconds {
+    use core::convert::TryFrom;
+    use core::fmt;
+
+    use serde_core::de::{Deserializer, Error, Visitor};
+    use serde_core::ser::Serializer;
+
+    use super::OUT_OF_RANGE;
+    use crate::Timestamp;
+
+    /// Serializes the timestamp as an `f64`.
+    #[inline]
+    pub fn serialize<S: Serializer>(ts: &Timestamp, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(ts.duration_since(Timestamp::UNIX_EPOCH).as_seconds_f64())
+    }
+
+    /// Deserializes the timestamp from a float or integer number of seconds.
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        struct FloatVisitor;
+
+        impl Visitor<'_> for FloatVisitor {
+            type Value = Timestamp;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a Unix timestamp in seconds")
+            }
+
+            #[inline]
+            fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+                // round to the nearest microsecond, as any finer digits are mostly float error
+                let micros = v * 1e6;
+
+                // also rejects NaN
+                if !(-(i64::MAX as f64)..i64::MAX as f64).contains(&micros) {
+                    return Err(E::custom(OUT_OF_RANGE));
+                }
+
+                let mut units = micros as i64;
+
+                match micros - units as f64 {
+                    rem if rem >= 0.5 => units += 1,
+                    rem if rem <= -0.5 => units -= 1,
+                    _ => {}
+                }
+
+                super::from_units(units, 1_000_000).ok_or_else(|| E::custom(OUT_OF_RANGE))
+            }
+
+            #[inline]
+            fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+                super::from_units(v, 1).ok_or_else(|| E::custom(OUT_OF_RANGE))
+            }
+
+            #[inline]
+            fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+                match i64::try_from(v) {
+                    Ok(v) => self.visit_i64(v),
+                    Err(_) => Err(E::custom(OUT_OF_RANGE)),
+                }
+            }
+        }
+
+        deserializer.deserialize_f64(FloatVisitor)
+    }
+}
+
+/// ISO8601 string as written by [`Timestamp::format`], even in binary formats.
+pub mod string {
+    use serde_core::de::{Deserializer, Error, Visitor};
+    use serde_core::ser::Serializer;
+
+    use crate::Timestamp;
+
+    struct StrVisitor;
+
+    impl Visitor<'_> for StrVisitor {
+        type Value = Timestamp;
+
+        #[inline]
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+            formatter.write_str("an ISO8601 Timestamp")
+        }
+
+        #[inline]
+        fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+            Timestamp::try_parse_or_expanded(v).map_err(E::custom)
+        }
+    }
+
+    /// Serializes the timestamp as a string.
+    #[inline]
+    pub fn serialize<S: Serializer>(ts: &Timestamp, serializer: S) -> Result<S::Ok, S::Error> {
+        match ts.format_expanded_fallback() {
+            Some(expanded) => serializer.serialize_str(&expanded),
+            None => serializer.serialize_str(&ts.format()),
+        }
+    }
+
+    /// Deserializes the timestamp from a string.
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        deserializer.deserialize_str(StrVisitor)
+    }
+}
+
+/// Full nanosecond precision, written as an ISO8601 string with 9 fractional digits in human-readable formats,
+/// and as a `(i64, u32)` tuple of whole seconds since the Unix Epoch and nanoseconds within that second
+/// in binary formats, such as bincode or postcard.
+///
+/// The regular [`Deserialize`](serde_core::Deserialize) for [`Timestamp`] reads this tuple in self-describing formats,
+/// and this module also reads the default integer milliseconds wherever the format can tell them apart.
+///
+/// Like the regular `Deserialize`, reading requires a self-describing format, such as JSON or CBOR. Formats that
+/// are not, such as bincode or postcard, can read the tuple with [`deserialize_tuple`](crate::serde::lossless::deserialize_tuple) instead, as in
+/// `#[serde(serialize_with = "lossless::serialize", deserialize_with = "lossless::deserialize_tuple")]`.
+pub mod lossless {
+    use core::convert::TryFrom;
+    use core::fmt;
+
+    use serde_core::de::{Deserializer, Error, SeqAccess, Visitor};
+    use serde_core::ser::{SerializeTuple, Serializer};
+
+    use super::OUT_OF_RANGE;
+    use crate::Timestamp;
+
+    /// Serializes the timestamp without losing precision.
+    #[inline]
+    pub fn serialize<S: Serializer>(ts: &Timestamp, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return match ts.format_expanded_fallback() {
+                Some(expanded) => serializer.serialize_str(&expanded),
+                None => serializer.serialize_str(&ts.format_nanoseconds()),
+            };
+        }
+
+        let (seconds, nanoseconds) = super::to_parts(ts);
+
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&seconds)?;
+        tuple.serialize_element(&nanoseconds)?;
+        tuple.end()
+    }
+
+    struct LosslessVisitor;
+
+    impl<'de> Visitor<'de> for LosslessVisitor {
+        type Value = Timestamp;
+
+        #[inline]
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an ISO8601 Timestamp")
+        }
+
+        #[inline]
+        fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+            Timestamp::try_parse_or_expanded(v).map_err(E::custom)
+        }
+
+        #[inline]
+        fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+            super::visit_parts(seq)
+        }
+
+        // the default encoding, in milliseconds
+
+        #[inline]
+        fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+            super::from_units(v, 1_000).ok_or_else(|| E::custom(OUT_OF_RANGE))
+        }
+
+        #[inline]
+        fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+            match i64::try_from(v) {
+                Ok(v) => self.visit_i64(v),
+                Err(_) => Err(E::custom(OUT_OF_RANGE)),
+            }
+        }
+    }
+
+    /// Deserializes the timestamp from either the lossless or default encodings, in self-describing formats.
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        deserializer.deserialize_any(LosslessVisitor)
+    }
+
+    /// Deserializes the timestamp from the lossless `(seconds, nanoseconds)` tuple alone,
+    /// for formats that are not self-describing.
+    #[inline]
+    pub fn deserialize_tuple<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        deserializer.deserialize_tuple(2, LosslessVisitor)
+    }
+}
+
+/// A [`Timestamp`] that is always serialized without losing precision, as described by [`lossless`],
+/// such as for values within collections where `#[serde(with = "...")]` cannot reach.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Lossless(pub Timestamp);
+
+impl serde_core::Serialize for Lossless {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        lossless::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> serde_core::Deserialize<'de> for Lossless {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        lossless::deserialize(deserializer).map(Lossless)
+    }
+}
+
+impl From<Timestamp> for Lossless {
+    #[inline(always)]
+    fn from(ts: Timestamp) -> Self {
+        Lossless(ts)
+    }
+}
+
+impl From<Lossless> for Timestamp {
+    #[inline(always)]
+    fn from(ts: Lossless) -> Self {
+        ts.0
+    }
+}
+
+/// Optional ISO8601 string, where `None` is written as an empty string, and both an empty string
+/// and a missing value are read as `None`.
+///
+/// Combine with `#[serde(default)]` to also accept a missing field.
+pub mod option_string {
+    use core::fmt;
+
+    use serde_core::de::{Deserializer, Error, Visitor};
+    use serde_core::ser::Serializer;
+
+    use crate::Timestamp;
+
+    /// Serializes the timestamp as a string, or `None` as an empty string.
+    #[inline]
+    pub fn serialize<S: Serializer>(ts: &Option<Timestamp>, serializer: S) -> Result<S::Ok, S::Error> {
+        match ts {
+            Some(ts) => super::string::serialize(ts, serializer),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    /// Deserializes the timestamp from a string, or `None` from an empty string or missing value.
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Timestamp>, D::Error> {
+        struct OptionVisitor;
+
+        impl<'de> Visitor<'de> for OptionVisitor {
+            type Value = Option<Timestamp>;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an ISO8601 Timestamp or empty string")
+            }
+
+            #[inline]
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                match v {
+                    "" => Ok(None),
+                    _ => Timestamp::try_parse_or_expanded(v).map(Some).map_err(E::custom),
+                }
+            }
+
+            #[inline]
+            fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            #[inline]
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            #[inline]
+            fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+                deserializer.deserialize_str(self)
+            }
+        }
+
+        deserializer.deserialize_option(OptionVisitor)
+    }
+}
+
+macro_rules! option_module {
+    ($(#[$meta:meta])* $name:ident, $inner:ident) => {
+        $(#[$meta])*
+        ///
+        /// Combine with `#[serde(default)]` to also accept a missing field.
+        pub mod $name {
+            use serde_core::de::{Deserialize, Deserializer};
+            use serde_core::ser::{Serialize, Serializer};
+
+            use crate::Timestamp;
+
+            struct With(Timestamp);
+
+            impl Serialize for With {
+                #[inline]
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    super::$inner::serialize(&self.0, serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for With {
+                #[inline]
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    super::$inner::deserialize(deserializer).map(With)
+                }
+            }
+
+            /// Serializes the timestamp, or `None` as a missing value.
+            #[inline]
+            pub fn serialize<S: Serializer>(ts: &Option<Timestamp>, serializer: S) -> Result<S::Ok, S::Error> {
+                match *ts {
+                    Some(ts) => serializer.serialize_some(&With(ts)),
+                    None => serializer.serialize_none(),
+                }
+            }
+
+            /// Deserializes the timestamp, or `None` from a missing value.
+            #[inline]
+            pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Timestamp>, D::Error> {
+                Ok(Option::<With>::deserialize(deserializer)?.map(|with| with.0))
+            }
+        }
+    };
+}
+
+option_module! {
+    /// Optional Unix timestamp in whole seconds, as [`unix_seconds`].
+    option_unix_seconds, unix_seconds
+}
+
+option_module! {
+    /// Optional Unix timestamp in milliseconds, as [`unix_millis`].
+    option_unix_millis, unix_millis
+}
+
+option_module! {
+    /// Optional Unix timestamp in microseconds, as [`unix_micros`].
+    option_unix_micros, unix_micros
+}
+
+option_module! {
+    /// Optional Unix timestamp in nanoseconds, as [`unix_nanos`].
+    option_unix_nanos, unix_nanos
+}
+
+option_module! {
+    /// Optional Unix timestamp in seconds as an `f64`, as [`float_seconds`].
+    option_float_seconds, float_seconds
+}
+
+option_module! {
+    /// Optional timestamp with full nanosecond precision, as [`lossless`].
+    option_lossless, lossless
+}
@@ -0,0 +1,93 @@
+use crate::ts_str::TrimFraction;
+
+/// A calendar format chosen at runtime, such as from configuration, for [`Timestamp::format_spec`](crate::Timestamp::format_spec).
+///
+/// The default matches [`Timestamp::format`](crate::Timestamp::format), and can be customized using the builder methods:
+///
+/// ```rust
+/// # use iso8601_timestamp::{FormatSpec, Timestamp};
+/// let ts = Timestamp::parse("2021-10-17T02:03:01.123456Z").unwrap();
+///
+/// let spec = FormatSpec::DEFAULT.space_separator(true).precision(6).utc_offset(true);
+///
+/// assert_eq!(ts.format_spec(&spec), "2021-10-17 02:03:01.123456+00:00");
+/// assert_eq!(ts.format_spec(&FormatSpec::DEFAULT), &*ts.format());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatSpec {
+    pub(crate) basic_format: bool,
+    pub(crate) separator: u8,
+    pub(crate) precision: u8,
+    pub(crate) decimal: u8,
+    pub(crate) utc_offset: bool,
+    pub(crate) trim: Option<TrimFraction>,
+}
+
+impl Default for FormatSpec {
+    #[inline]
+    fn default() -> Self {
+        FormatSpec::DEFAULT
+    }
+}
+
+impl FormatSpec {
+    /// `2021-10-17T02:03:01.123Z`, as written by [`Timestamp::format`](crate::Timestamp::format).
+    pub const DEFAULT: Self = FormatSpec {
+        basic_format: false,
+        separator: b'T',
+        precision: 3,
+        decimal: b'.',
+        utc_offset: false,
+        trim: None,
+    };
+
+    /// Write the basic format without `-` and `:` separators in the date and time, e.g. `20211017T020301.123Z`.
+    #[inline]
+    #[must_use]
+    pub const fn basic_format(mut self, basic: bool) -> Self {
+        self.basic_format = basic;
+        self
+    }
+
+    /// Separate the date and time with a space rather than `T`, as SQL databases do,
+    /// e.g. `2021-10-17 02:03:01.123Z`.
+    #[inline]
+    #[must_use]
+    pub const fn space_separator(mut self, space: bool) -> Self {
+        self.separator = if space { b' ' } else { b'T' };
+        self
+    }
+
+    /// Set the number of fractional second digits, at most 9. Zero omits the fraction entirely.
+    #[inline]
+    #[must_use]
+    pub const fn precision(mut self, digits: u8) -> Self {
+        self.precision = if digits > 9 { 9 } else { digits };
+        self
+    }
+
+    /// Write `,` rather than `.` before the fractional seconds, e.g. `2021-10-17T02:03:01,123Z`.
+    #[inline]
+    #[must_use]
+    pub const fn decimal_comma(mut self, comma: bool) -> Self {
+        self.decimal = if comma { b',' } else { b'.' };
+        self
+    }
+
+    /// Write UTC as `+00:00` rather than `Z`.
+    #[inline]
+    #[must_use]
+    pub const fn utc_offset(mut self, offset: bool) -> Self {
+        self.utc_offset = offset;
+        self
+    }
+
+    /// Remove trailing zeros from the fractional seconds, as described by [`TrimFraction`],
+    /// or `None` to always write every digit.
+    #[inline]
+    #[must_use]
+    pub const fn trim(mut self, trim: Option<TrimFraction>) -> Self {
+        self.trim = trim;
+        self
+    }
+}
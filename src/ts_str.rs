@@ -11,24 +11,152 @@ type I<BOOL> = t::UInt<t::UTerm, BOOL>;
 
 // 4 bytes for full-formatting (--::)
 type F4<F> = t::Prod<I<F>, t::U4>;
-// 5 bytes for offset (00:00)
-type O5<O> = t::Prod<I<O>, t::U5>;
+// 5 bytes for offset (00:00) in full-formatting, or 4 bytes (0000) otherwise
+type O5<F, O> = t::Sum<t::Prod<I<O>, t::U4>, t::Prod<I<O>, I<F>>>;
 // '+' + 4Y + 2M + 2D + T + 2H + 2m + 2s + Z
 type P17<P> = t::Sum<P, t::U17>;
 type P18<P> = t::Sum<P17<P>, I<t::Gr<P, t::U0>>>; // accounts for . that's only present when P>0
-type F4O5<F, O> = t::Sum<F4<F>, O5<O>>;
+type F4O5<F, O> = t::Sum<F4<F>, O5<F, O>>;
 
 type StrLen<F, O, P> = t::Sum<P18<P>, F4O5<F, O>>;
 
+// 3 bytes for full-formatting (-::), as ordinal dates have no month
+type F3<F> = t::Prod<I<F>, t::U3>;
+// '+' + 4Y + 3D + T + 2H + 2m + 2s + Z
+type P16<P> = t::Sum<P, t::U16>;
+type P17O<P> = t::Sum<P16<P>, I<t::Gr<P, t::U0>>>; // accounts for . that's only present when P>0
+type F3O5<F, O> = t::Sum<F3<F>, O5<F, O>>;
+
+type OrdinalStrLen<F, O, P> = t::Sum<P17O<P>, F3O5<F, O>>;
+
+// 3 bytes for offset seconds (:00) in full-formatting, or 2 bytes (00) otherwise
+type OS<F> = t::Sum<t::U2, I<F>>;
+
 #[doc(hidden)]
 pub struct FormatString<F, O, P>(PhantomData<(F, O, P)>);
 
 impl<F, O, P> sealed::Sealed for FormatString<F, O, P> {}
 
+#[doc(hidden)]
+pub struct OrdinalFormatString<F, O, P>(PhantomData<(F, O, P)>);
+
+impl<F, O, P> sealed::Sealed for OrdinalFormatString<F, O, P> {}
+
+#[doc(hidden)]
+pub struct WeekFormatString<F, O, P>(PhantomData<(F, O, P)>);
+
+impl<F, O, P> sealed::Sealed for WeekFormatString<F, O, P> {}
+
+#[cfg(feature = "large-dates")]
+#[doc(hidden)]
+pub struct ExpandedFormatString<F, O, P, Y>(PhantomData<(F, O, P, Y)>);
+
+#[cfg(feature = "large-dates")]
+impl<F, O, P, Y> sealed::Sealed for ExpandedFormatString<F, O, P, Y> {}
+
+#[doc(hidden)]
+pub struct DateFormatString<F>(PhantomData<F>);
+
+impl<F> sealed::Sealed for DateFormatString<F> {}
+
+#[doc(hidden)]
+pub struct OffsetSecondsFormatString<F, P>(PhantomData<(F, P)>);
+
+impl<F, P> sealed::Sealed for OffsetSecondsFormatString<F, P> {}
+
+#[doc(hidden)]
+pub struct TrimmedFormatString<F, O, P>(PhantomData<(F, O, P)>);
+
+impl<F, O, P> sealed::Sealed for TrimmedFormatString<F, O, P> {}
+
+#[doc(hidden)]
+pub struct SpecFormatString;
+
+impl sealed::Sealed for SpecFormatString {}
+
+#[doc(hidden)]
+pub struct HttpDateFormatString;
+
+impl sealed::Sealed for HttpDateFormatString {}
+
+#[doc(hidden)]
+pub struct DurationFormatString;
+
+impl sealed::Sealed for DurationFormatString {}
+
+#[doc(hidden)]
+pub struct IntervalFormatString;
+
+impl sealed::Sealed for IntervalFormatString {}
+
+#[doc(hidden)]
+pub struct RepeatingIntervalFormatString;
+
+impl sealed::Sealed for RepeatingIntervalFormatString {}
+
+/// How trailing zeros are removed from the fractional seconds by [`Timestamp::format_trimmed_raw`](crate::Timestamp::format_trimmed_raw).
+///
+/// Either way, the `.` is removed too when no digits are left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrimFraction {
+    /// Remove every trailing zero, as with Go's `RFC3339Nano`, e.g. `.12`
+    Zeros,
+    /// Keep 0, 3, 6 or 9 digits, as with protobuf JSON, e.g. `.120`
+    Groups,
+}
+
+/// Storage for formats whose length depends on the value, such as optional offset seconds.
+///
+/// Templates are written into the full buffer, then `len` is trimmed to the written length.
+#[doc(hidden)]
+pub struct VarStorage<N: ArrayLength> {
+    pub(crate) buf: GenericArray<u8, N>,
+    pub(crate) len: u8,
+}
+
+impl<N: ArrayLength> Clone for VarStorage<N>
+where
+    GenericArray<u8, N>: Copy,
+{
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N: ArrayLength> Copy for VarStorage<N> where GenericArray<u8, N>: Copy {}
+
+impl<N: ArrayLength> Default for VarStorage<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        VarStorage {
+            buf: GenericArray::default(),
+            len: N::USIZE as u8,
+        }
+    }
+}
+
+impl<N: ArrayLength> AsRef<[u8]> for VarStorage<N> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.buf[..self.len as usize]
+    }
+}
+
+impl<N: ArrayLength> AsMut<[u8]> for VarStorage<N> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.buf[..self.len as usize]
+    }
+}
+
 #[doc(hidden)]
 pub trait IsValidFormat: sealed::Sealed {
     type Length: ArrayLength;
     type Storage: AsRef<[u8]> + AsMut<[u8]> + Clone + Copy + Default;
+
+    /// Whether the leading `+` sign is kept for positive years, as required by expanded years.
+    const ALWAYS_SIGNED: bool = false;
 }
 
 impl<F, O, P> IsValidFormat for FormatString<F, O, P>
@@ -36,9 +164,10 @@ where
     F: t::Bit,
     I<F>: Mul<t::U4>,
     O: t::Bit,
-    I<O>: Mul<t::U5>,
+    I<O>: Mul<t::U4> + Mul<I<F>>,
+    t::Prod<I<O>, t::U4>: Add<t::Prod<I<O>, I<F>>>,
     P: t::Unsigned + Add<t::U17> + t::IsLessOrEqual<t::U9, Output = t::True> + t::IsGreater<t::U0>,
-    F4<F>: Add<O5<O>>,
+    F4<F>: Add<O5<F, O>>,
     P17<P>: Add<I<t::Gr<P, t::U0>>>,
     P18<P>: Add<F4O5<F, O>>,
     StrLen<F, O, P>: ArrayLength,
@@ -49,6 +178,129 @@ where
     type Storage = GenericArray<u8, Self::Length>;
 }
 
+impl<F, O, P> IsValidFormat for OrdinalFormatString<F, O, P>
+where
+    F: t::Bit,
+    I<F>: Mul<t::U3>,
+    O: t::Bit,
+    I<O>: Mul<t::U4> + Mul<I<F>>,
+    t::Prod<I<O>, t::U4>: Add<t::Prod<I<O>, I<F>>>,
+    P: t::Unsigned + Add<t::U16> + t::IsLessOrEqual<t::U9, Output = t::True> + t::IsGreater<t::U0>,
+    F3<F>: Add<O5<F, O>>,
+    P16<P>: Add<I<t::Gr<P, t::U0>>>,
+    P17O<P>: Add<F3O5<F, O>>,
+    OrdinalStrLen<F, O, P>: ArrayLength,
+
+    <OrdinalStrLen<F, O, P> as ArrayLength>::ArrayType<u8>: Copy,
+{
+    type Length = OrdinalStrLen<F, O, P>;
+    type Storage = GenericArray<u8, Self::Length>;
+}
+
+// `YYYY-Www-D` has the same length as `YYYY-MM-DD`, and `YYYYWwwD` as `YYYYMMDD`
+impl<F, O, P> IsValidFormat for WeekFormatString<F, O, P>
+where
+    FormatString<F, O, P>: IsValidFormat,
+{
+    type Length = <FormatString<F, O, P> as IsValidFormat>::Length;
+    type Storage = <FormatString<F, O, P> as IsValidFormat>::Storage;
+}
+
+// expanded years add `Y` extra digits to the calendar format
+#[cfg(feature = "large-dates")]
+impl<F, O, P, Y> IsValidFormat for ExpandedFormatString<F, O, P, Y>
+where
+    FormatString<F, O, P>: IsValidFormat,
+    Y: t::Unsigned + t::IsLessOrEqual<t::U2, Output = t::True> + t::IsGreater<t::U0, Output = t::True>,
+    <FormatString<F, O, P> as IsValidFormat>::Length: Add<Y>,
+    t::Sum<<FormatString<F, O, P> as IsValidFormat>::Length, Y>: ArrayLength,
+
+    <t::Sum<<FormatString<F, O, P> as IsValidFormat>::Length, Y> as ArrayLength>::ArrayType<u8>: Copy,
+{
+    type Length = t::Sum<<FormatString<F, O, P> as IsValidFormat>::Length, Y>;
+    type Storage = GenericArray<u8, Self::Length>;
+
+    const ALWAYS_SIGNED: bool = true;
+}
+
+// '+' + 4Y + 2M + 2D, with 2 bytes for full-formatting (--)
+impl<F> IsValidFormat for DateFormatString<F>
+where
+    F: t::Bit,
+    I<F>: Mul<t::U2>,
+    t::U9: Add<t::Prod<I<F>, t::U2>>,
+    t::Sum<t::U9, t::Prod<I<F>, t::U2>>: ArrayLength,
+
+    <t::Sum<t::U9, t::Prod<I<F>, t::U2>> as ArrayLength>::ArrayType<u8>: Copy,
+{
+    type Length = t::Sum<t::U9, t::Prod<I<F>, t::U2>>;
+    type Storage = GenericArray<u8, Self::Length>;
+}
+
+// offset seconds add up to 3 bytes (`:ss`) to the calendar format with offset, or 2 bytes (`ss`) without punctuation
+impl<F, P> IsValidFormat for OffsetSecondsFormatString<F, P>
+where
+    F: t::Bit,
+    t::U2: Add<I<F>>,
+    FormatString<F, t::True, P>: IsValidFormat,
+    <FormatString<F, t::True, P> as IsValidFormat>::Length: Add<OS<F>>,
+    t::Sum<<FormatString<F, t::True, P> as IsValidFormat>::Length, OS<F>>: ArrayLength,
+
+    <t::Sum<<FormatString<F, t::True, P> as IsValidFormat>::Length, OS<F>> as ArrayLength>::ArrayType<u8>:
+        Copy,
+{
+    type Length = t::Sum<<FormatString<F, t::True, P> as IsValidFormat>::Length, OS<F>>;
+    type Storage = VarStorage<Self::Length>;
+}
+
+// trimming the fraction can only make the calendar format shorter
+impl<F, O, P> IsValidFormat for TrimmedFormatString<F, O, P>
+where
+    FormatString<F, O, P>: IsValidFormat,
+    <<FormatString<F, O, P> as IsValidFormat>::Length as ArrayLength>::ArrayType<u8>: Copy,
+{
+    type Length = <FormatString<F, O, P> as IsValidFormat>::Length;
+    type Storage = VarStorage<Self::Length>;
+}
+
+// a runtime format spec can be at most as long as the longest calendar format
+impl IsValidFormat for SpecFormatString {
+    type Length = <FormatString<t::True, t::True, t::U9> as IsValidFormat>::Length;
+    type Storage = VarStorage<Self::Length>;
+}
+
+// `Sun, 17 Oct 2021 02:03:01 GMT`
+impl IsValidFormat for HttpDateFormatString {
+    type Length = t::U29;
+    type Storage = GenericArray<u8, t::U29>;
+}
+
+// `-P4294967295Y4294967295M4294967295W4294967295DT4294967295H4294967295M4294967296.999999999S`
+impl IsValidFormat for DurationFormatString {
+    type Length = t::U90;
+    type Storage = VarStorage<t::U90>;
+}
+
+// a formatted timestamp and duration, either way around, or two timestamps
+#[cfg(not(feature = "large-dates"))]
+impl IsValidFormat for IntervalFormatString {
+    type Length = t::Sum<t::U90, t::U26>;
+    type Storage = VarStorage<Self::Length>;
+}
+
+// timestamps with expanded years, `-012021-10-17T02:03:01.123456789Z`, take up to 33 bytes
+#[cfg(feature = "large-dates")]
+impl IsValidFormat for IntervalFormatString {
+    type Length = t::Sum<t::U90, t::U34>;
+    type Storage = VarStorage<Self::Length>;
+}
+
+// `R4294967295/` and an interval
+impl IsValidFormat for RepeatingIntervalFormatString {
+    type Length = t::Sum<<IntervalFormatString as IsValidFormat>::Length, t::U12>;
+    type Storage = VarStorage<Self::Length>;
+}
+
 #[allow(unused_assignments)]
 #[inline(always)]
 #[rustfmt::skip]
@@ -83,16 +335,16 @@ where
         (true,  false, 7) => w!(b"+0000-00-00T00:00:00.0000000Z"),
         (true,  false, 8) => w!(b"+0000-00-00T00:00:00.00000000Z"),
         (true,  false, 9) => w!(b"+0000-00-00T00:00:00.000000000Z"),
-        (false, true,  0) => w!(b"+00000000T000000+00:00"),
-        (false, true,  1) => w!(b"+00000000T000000.0+00:00"),
-        (false, true,  2) => w!(b"+00000000T000000.00+00:00"),
-        (false, true,  3) => w!(b"+00000000T000000.000+00:00"),
-        (false, true,  4) => w!(b"+00000000T000000.0000+00:00"),
-        (false, true,  5) => w!(b"+00000000T000000.00000+00:00"),
-        (false, true,  6) => w!(b"+00000000T000000.000000+00:00"),
-        (false, true,  7) => w!(b"+00000000T000000.0000000+00:00"),
-        (false, true,  8) => w!(b"+00000000T000000.00000000+00:00"),
-        (false, true,  9) => w!(b"+00000000T000000.000000000+00:00"),
+        (false, true,  0) => w!(b"+00000000T000000+0000"),
+        (false, true,  1) => w!(b"+00000000T000000.0+0000"),
+        (false, true,  2) => w!(b"+00000000T000000.00+0000"),
+        (false, true,  3) => w!(b"+00000000T000000.000+0000"),
+        (false, true,  4) => w!(b"+00000000T000000.0000+0000"),
+        (false, true,  5) => w!(b"+00000000T000000.00000+0000"),
+        (false, true,  6) => w!(b"+00000000T000000.000000+0000"),
+        (false, true,  7) => w!(b"+00000000T000000.0000000+0000"),
+        (false, true,  8) => w!(b"+00000000T000000.00000000+0000"),
+        (false, true,  9) => w!(b"+00000000T000000.000000000+0000"),
         (false, false, 0) => w!(b"+00000000T000000Z"),
         (false, false, 1) => w!(b"+00000000T000000.0Z"),
         (false, false, 2) => w!(b"+00000000T000000.00Z"),
@@ -111,6 +363,116 @@ where
     value
 }
 
+/// Writes the `T00:00:00.000+00:00` portion of a template, shared by the non-calendar date layouts.
+#[inline(always)]
+fn time_template<F: t::Bit, O: t::Bit, P: t::Unsigned>(buf: &mut [u8]) {
+    let time: &[u8] = if F::BOOL { b"T00:00:00" } else { b"T000000" };
+    buf[..time.len()].copy_from_slice(time);
+
+    let mut pos = time.len();
+
+    if P::USIZE > 0 {
+        buf[pos] = b'.';
+        buf[pos + 1..pos + 1 + P::USIZE].fill(b'0');
+        pos += 1 + P::USIZE;
+    }
+
+    match O::BOOL {
+        true if F::BOOL => buf[pos..].copy_from_slice(b"+00:00"),
+        true => buf[pos..].copy_from_slice(b"+0000"),
+        false => buf[pos] = b'Z',
+    }
+}
+
+#[inline(always)]
+pub fn ordinal_template<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+) -> <OrdinalFormatString<F, O, P> as IsValidFormat>::Storage
+where
+    OrdinalFormatString<F, O, P>: IsValidFormat,
+{
+    let mut value: <OrdinalFormatString<F, O, P> as IsValidFormat>::Storage = Default::default();
+    let buf = value.as_mut();
+
+    let date: &[u8] = if F::BOOL { b"+0000-000" } else { b"+0000000" };
+    buf[..date.len()].copy_from_slice(date);
+    time_template::<F, O, P>(&mut buf[date.len()..]);
+
+    value
+}
+
+#[inline(always)]
+pub fn week_template<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+) -> <WeekFormatString<F, O, P> as IsValidFormat>::Storage
+where
+    WeekFormatString<F, O, P>: IsValidFormat,
+{
+    let mut value: <WeekFormatString<F, O, P> as IsValidFormat>::Storage = Default::default();
+    let buf = value.as_mut();
+
+    let date: &[u8] = if F::BOOL { b"+0000-W00-0" } else { b"+0000W000" };
+    buf[..date.len()].copy_from_slice(date);
+    time_template::<F, O, P>(&mut buf[date.len()..]);
+
+    value
+}
+
+#[cfg(feature = "large-dates")]
+#[inline(always)]
+pub fn expanded_template<F: t::Bit, O: t::Bit, P: t::Unsigned, Y: t::Unsigned>(
+) -> <ExpandedFormatString<F, O, P, Y> as IsValidFormat>::Storage
+where
+    FormatString<F, O, P>: IsValidFormat,
+    ExpandedFormatString<F, O, P, Y>: IsValidFormat,
+{
+    let mut value: <ExpandedFormatString<F, O, P, Y> as IsValidFormat>::Storage = Default::default();
+    let buf = value.as_mut();
+
+    // `+` and extra digits, followed by the regular calendar template without its sign
+    buf[..1 + Y::USIZE].fill(b'0');
+    buf[0] = b'+';
+    buf[1 + Y::USIZE..].copy_from_slice(&template::<F, O, P>().as_ref()[1..]);
+
+    value
+}
+
+#[inline(always)]
+pub fn offset_seconds_template<F: t::Bit, P: t::Unsigned>(
+) -> <OffsetSecondsFormatString<F, P> as IsValidFormat>::Storage
+where
+    FormatString<F, t::True, P>: IsValidFormat,
+    OffsetSecondsFormatString<F, P>: IsValidFormat,
+{
+    let mut value: <OffsetSecondsFormatString<F, P> as IsValidFormat>::Storage = Default::default();
+    let buf = value.as_mut();
+
+    // the regular calendar template with offset, followed by `:00` or `00` for the seconds
+    let seconds: &[u8] = if F::BOOL { b":00" } else { b"00" };
+    let (calendar, rest) = buf.split_at_mut(buf.len() - seconds.len());
+    calendar.copy_from_slice(template::<F, t::True, P>().as_ref());
+    rest.copy_from_slice(seconds);
+
+    value
+}
+
+#[inline(always)]
+pub fn trimmed_template<F: t::Bit, O: t::Bit, P: t::Unsigned>(
+) -> <TrimmedFormatString<F, O, P> as IsValidFormat>::Storage
+where
+    FormatString<F, O, P>: IsValidFormat,
+    TrimmedFormatString<F, O, P>: IsValidFormat,
+{
+    let mut value: <TrimmedFormatString<F, O, P> as IsValidFormat>::Storage = Default::default();
+    value.as_mut().copy_from_slice(template::<F, O, P>().as_ref());
+    value
+}
+
+#[inline(always)]
+pub fn http_date_template() -> <HttpDateFormatString as IsValidFormat>::Storage {
+    let mut value: <HttpDateFormatString as IsValidFormat>::Storage = Default::default();
+    value.copy_from_slice(b"Mon, 00 Jan 0000 00:00:00 GMT");
+    value
+}
+
 /// Fixed-size inline string storage that exactly fits the formatted timestamp.
 #[derive(Clone, Copy)]
 #[repr(transparent)]
@@ -120,10 +482,12 @@ impl<S: IsValidFormat> TimestampStr<S> {
     /// The maximum length this timestamp string can be in bytes (and characters).
     ///
     /// If the timestamp is positive, that is after the year 0000, the formatted
-    /// string will be `MAX_LEN - 1` as it will not print the `+` sign in front.
+    /// string will be `MAX_LEN - 1` as it will not print the `+` sign in front,
+    /// unless the format uses expanded years, which are always signed.
     ///
-    /// This value is equal to `core::mem::size_of::<TimestampStr<S>>()`,
+    /// For fixed-length formats, this value is equal to `core::mem::size_of::<TimestampStr<S>>()`,
     /// as the internal representation of `TimestampStr` is just `[u8; MAX_LEN]`.
+    /// Formats with optional offset seconds may be 3 bytes shorter, and store their length alongside.
     pub const MAX_LEN: usize = <S::Length as t::Unsigned>::USIZE;
 }
 
@@ -143,6 +507,14 @@ mod ts_str_tests {
         assert_size_of::<f::FullMillisecondsOffset>();
         assert_size_of::<f::FullNanoseconds>();
         assert_size_of::<f::ShortMilliseconds>();
+        assert_size_of::<f::ShortMillisecondsOffset>();
+        assert_size_of::<f::FullDate>();
+        assert_size_of::<f::ShortDate>();
+        assert_size_of::<f::FullOrdinalMilliseconds>();
+        assert_size_of::<f::FullWeekMilliseconds>();
+        assert_size_of::<f::HttpDate>();
+        #[cfg(feature = "large-dates")]
+        assert_size_of::<f::FullExpandedMilliseconds>();
     }
 }
 
@@ -154,7 +526,7 @@ impl<S: IsValidFormat> AsRef<str> for TimestampStr<S> {
         unsafe {
             // skip + sign if positive
             let bytes = self.0.as_ref();
-            let is_positive = *bytes.get_unchecked(0) == b'+';
+            let is_positive = !S::ALWAYS_SIGNED && *bytes.get_unchecked(0) == b'+';
             core::str::from_utf8_unchecked(bytes.get_unchecked(is_positive as usize..))
         }
     }
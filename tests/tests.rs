@@ -25,6 +25,7 @@ fn test_parse_iso8601_variations() {
         "20211017T0203z", // without seconds
         "20211017T020301.123Z",
         "20211017T020301.123+00:00",
+        "20211017T020301.123+0000",
         "20211017T020301.123uTc",
         "2013-10-07 08:23:19.120Z",
         "2013-10-07T08:23:19.120Z",
@@ -157,14 +158,1274 @@ fn test_all_formats() {
     test_cfg!(t::True, t::True, t::U8, offset, "2014-04-12T16:00:00.00000000+15:30");
     test_cfg!(t::True, t::True, t::U9, offset, "2014-04-12T16:00:00.000000000+15:30");
 
-    test_cfg!(t::False, t::True, t::U0, offset, "20140412T160000+15:30");
-    test_cfg!(t::False, t::True, t::U1, offset, "20140412T160000.0+15:30");
-    test_cfg!(t::False, t::True, t::U2, offset, "20140412T160000.00+15:30");
-    test_cfg!(t::False, t::True, t::U3, offset, "20140412T160000.000+15:30");
-    test_cfg!(t::False, t::True, t::U4, offset, "20140412T160000.0000+15:30");
-    test_cfg!(t::False, t::True, t::U5, offset, "20140412T160000.00000+15:30");
-    test_cfg!(t::False, t::True, t::U6, offset, "20140412T160000.000000+15:30");
-    test_cfg!(t::False, t::True, t::U7, offset, "20140412T160000.0000000+15:30");
-    test_cfg!(t::False, t::True, t::U8, offset, "20140412T160000.00000000+15:30");
-    test_cfg!(t::False, t::True, t::U9, offset, "20140412T160000.000000000+15:30");
+    test_cfg!(t::False, t::True, t::U0, offset, "20140412T160000+1530");
+    test_cfg!(t::False, t::True, t::U1, offset, "20140412T160000.0+1530");
+    test_cfg!(t::False, t::True, t::U2, offset, "20140412T160000.00+1530");
+    test_cfg!(t::False, t::True, t::U3, offset, "20140412T160000.000+1530");
+    test_cfg!(t::False, t::True, t::U4, offset, "20140412T160000.0000+1530");
+    test_cfg!(t::False, t::True, t::U5, offset, "20140412T160000.00000+1530");
+    test_cfg!(t::False, t::True, t::U6, offset, "20140412T160000.000000+1530");
+    test_cfg!(t::False, t::True, t::U7, offset, "20140412T160000.0000000+1530");
+    test_cfg!(t::False, t::True, t::U8, offset, "20140412T160000.00000000+1530");
+    test_cfg!(t::False, t::True, t::U9, offset, "20140412T160000.000000000+1530");
+}
+
+#[test]
+fn test_parse_errors() {
+    use iso8601_timestamp::ParseErrorKind as K;
+
+    let fixtures: &[(&str, usize, K)] = &[
+        ("", 0, K::Empty),
+        ("2021-10-1", 9, K::UnexpectedEnd),
+        ("2021-13-17T02:03:01Z", 5, K::MonthOutOfRange),
+        ("2021-02-30T02:03:01Z", 8, K::InvalidDayForMonth),
+        ("2021-10-17X02:03:01Z", 10, K::UnexpectedCharacter),
+        ("2021-10-17T25:03:01Z", 11, K::HourOutOfRange),
+        ("2021-10-17T02:63:01Z", 14, K::MinuteOutOfRange),
+        ("2021-10-17T02:03:01Zabc", 20, K::TrailingCharacters),
+        ("2021-10-17T02:03:01+10:00abc", 25, K::TrailingCharacters),
+        ("2021-10-17T02:03:01+30:00", 19, K::InvalidOffset),
+        ("2021-10-17T02:03:01UTX", 19, K::UnexpectedCharacter),
+        ("2021-10-17T02:03:01Q", 19, K::UnexpectedCharacter),
+    ];
+
+    for &(input, offset, kind) in fixtures {
+        let err = Timestamp::try_parse(input).unwrap_err();
+
+        assert_eq!((err.offset(), err.kind()), (offset, kind), "{input}");
+    }
+}
+
+#[cfg(feature = "verify")]
+#[test]
+fn test_parse_error_digit() {
+    let err = "2021-1x-17T02:03:01Z".parse::<Timestamp>().unwrap_err();
+
+    assert_eq!(err.kind(), iso8601_timestamp::ParseErrorKind::InvalidDigit);
+    assert_eq!(err.offset(), 6);
+}
+
+#[test]
+fn test_parse_options_profiles() {
+    use iso8601_timestamp::{ParseOptions, TimezoneRule};
+
+    let rfc3339 = ParseOptions::RFC3339;
+    let xsd = ParseOptions::XSD;
+    let html = ParseOptions::HTML_DATETIME_LOCAL;
+
+    #[rustfmt::skip]
+    let fixtures: &[(&str, bool, bool, bool)] = &[
+        // input                                RFC3339 XSD    HTML
+        ("2021-10-17T02:03:01Z",                true,  true,  false),
+        ("2021-10-17t02:03:01z",                true,  false, false),
+        ("2021-10-17T02:03:01.123456789+10:00", true,  true,  false),
+        ("2021-10-17T02:03:01-04:30",           true,  true,  false),
+        ("2021-10-17T02:03:01",                 false, true,  true),
+        ("2021-10-17T02:03:01.123",             false, true,  true),
+        ("2021-10-17T02:03:01.1234",            false, true,  false),
+        ("2021-10-17 02:03:01",                 false, false, true),
+        ("2021-10-17T02:03",                    false, false, true),
+        ("-2021-10-17T02:03:01Z",               false, true,  false),
+        ("+2021-10-17T02:03:01Z",               false, false, false),
+        ("2021-10-17",                          false, false, false),
+        ("20211017T020301Z",                    false, false, false),
+        ("2021-10-17T0203:01Z",                 false, false, false),
+        ("2021-10-17T02:0301Z",                 false, false, false),
+        ("2021-10-17T02:03:01+1000",            false, false, false),
+        ("2021-10-17T02:03:01.Z",               false, false, false),
+        ("2021-10-17T02:03:01,123Z",            false, false, false),
+        ("2021-10-17T02:03:01UTC",              false, false, false),
+        ("2021-10-17_02:03:01Z",                false, false, false),
+        ("2021-10-17T02:03:01−04:00",           false, false, false),
+    ];
+
+    for &(input, a, b, c) in fixtures {
+        assert_eq!(
+            Timestamp::parse_with_options(input, &rfc3339).is_ok(),
+            a,
+            "RFC3339: {input}"
+        );
+        assert_eq!(
+            Timestamp::parse_with_options(input, &xsd).is_ok(),
+            b,
+            "XSD: {input}"
+        );
+        assert_eq!(
+            Timestamp::parse_with_options(input, &html).is_ok(),
+            c,
+            "HTML: {input}"
+        );
+
+        // everything is accepted by the lenient profile
+        assert_eq!(
+            Timestamp::parse_with_options(input, &ParseOptions::LENIENT).ok(),
+            Timestamp::parse(input)
+        );
+    }
+
+    let strict_digits = ParseOptions::LENIENT.check_digits(true);
+    assert!(Timestamp::parse_with_options("2021-1x-17", &strict_digits).is_err());
+
+    let no_tz = ParseOptions::LENIENT.timezone(TimezoneRule::Required);
+    assert!(Timestamp::parse_with_options("2021-10-17T02:03:01", &no_tz).is_err());
+    assert!(Timestamp::parse_with_options("2021-10-17T02:03:01uTc", &no_tz).is_ok());
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_parse_with_offset() {
+    use generic_array::typenum as t;
+    use iso8601_timestamp::ParsedOffset;
+
+    let fixtures = [
+        "2021-10-17T02:03:01+10:00",
+        "2021-10-17T02:03:01-04:30",
+        "2021-10-17T02:03:01+00:00",
+        "2021-10-17T02:03:01-00:00",
+        "2021-01-01T00:30:00+01:00",
+        "2021-10-17T02:03:01Z",
+        "2021-10-17T02:03:01",
+    ];
+
+    for fixture in fixtures {
+        let (ts, offset) = Timestamp::parse_with_offset(fixture).unwrap();
+
+        assert_eq!(ts, Timestamp::parse(fixture).unwrap());
+        assert_eq!(ts.format_at_offset::<t::U0>(offset), fixture);
+    }
+
+    // every kind of offset survives a round trip
+    let ts = Timestamp::parse("2021-10-17T02:03:01.123456789Z").unwrap();
+
+    for offset in [
+        ParsedOffset::Fixed(UtcOffset::from_hms(-4, -30, 0).unwrap()),
+        ParsedOffset::Utc,
+        ParsedOffset::Unspecified,
+        ParsedOffset::UnknownLocal,
+    ] {
+        assert_eq!(Timestamp::parse_with_offset(&ts.format_at_offset::<t::U9>(offset)), Ok((ts, offset)), "{offset:?}");
+    }
+
+    // local times beyond the supported range fall back to UTC
+    let (max, offset) = Timestamp::parse_with_offset("9999-12-31T23:59:59+01:00").unwrap();
+    assert_eq!(max.format_at_offset::<t::U0>(offset), "9999-12-31T23:59:59+01:00");
+
+    let max = Timestamp::parse("9999-12-31T23:59:59Z").unwrap();
+    assert_eq!(max.format_at_offset::<t::U0>(offset), "9999-12-31T23:59:59Z");
+
+    let offset = |s| Timestamp::parse_with_offset(s).unwrap().1;
+
+    assert_eq!(offset("2021-10-17T02:03:01Z"), ParsedOffset::Utc);
+    assert_eq!(offset("2021-10-17T02:03:01UTC"), ParsedOffset::Utc);
+    assert_eq!(offset("2021-10-17T02:03:01"), ParsedOffset::Unspecified);
+    assert_eq!(offset("2021-10-17"), ParsedOffset::Unspecified);
+    assert_eq!(offset("2021-10-17T02:03:01-00:00"), ParsedOffset::UnknownLocal);
+    assert_eq!(offset("2021-10-17T02:03:01+00:00"), ParsedOffset::Fixed(UtcOffset::UTC));
+    assert_eq!(offset("2021-10-17T02:03:01−04:30"), ParsedOffset::Fixed(UtcOffset::from_hms(-4, -30, 0).unwrap()));
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_offset_precision() {
+    use generic_array::typenum as t;
+    use iso8601_timestamp::{ParseErrorKind, ParseOptions};
+
+    let ts = Timestamp::parse("2021-10-16T21:03:01Z").unwrap();
+
+    for fixture in ["2021-10-17T02:03:01+05", "2021-10-17T02:03:01+0500", "2021-10-17T02:03:01+05:00", "2021-10-17T02:03:01+05:00:00", "20211017T020301+050000"] {
+        assert_eq!(Timestamp::parse(fixture), Some(ts), "{fixture}");
+    }
+
+    // historical local mean time, e.g. Europe/Dublin before 1880
+    let (lmt, offset) = Timestamp::parse_with_offset("1847-12-01T00:00:00-00:25:21").unwrap();
+    assert_eq!(lmt, Timestamp::parse("1847-12-01T00:25:21Z").unwrap());
+    assert_eq!(offset.utc_offset(), UtcOffset::from_hms(0, -25, -21).unwrap());
+    assert_eq!(lmt.format_at_offset::<t::U0>(offset), "1847-12-01T00:00:00-00:25:21");
+    assert_eq!(lmt.format_with_offset_seconds(offset.utc_offset()), "1847-12-01T00:25:21.000-00:25:21");
+    assert_eq!(lmt.format_offset_seconds_raw::<t::False, t::U0>(offset.utc_offset()), "18471201T002521-002521");
+    assert_eq!(lmt.format_with_offset_seconds(UtcOffset::UTC), "1847-12-01T00:25:21.000+00:00");
+
+    let err = |s, opts| Timestamp::parse_with_options(s, opts).unwrap_err().kind();
+
+    assert_eq!(err("2021-10-17T02:03:01+05", &ParseOptions::RFC3339), ParseErrorKind::UnexpectedEnd);
+    assert_eq!(err("2021-10-17T02:03:01+0500", &ParseOptions::RFC3339), ParseErrorKind::InvalidOffset);
+    assert_eq!(err("2021-10-17T02:03:01+05:00:00", &ParseOptions::RFC3339), ParseErrorKind::TrailingCharacters);
+    assert_eq!(err("2021-10-17T02:03:01+05:00:60", &ParseOptions::LENIENT), ParseErrorKind::InvalidOffset);
+
+    let opts = ParseOptions::RFC3339.allow_offset_hours_only(true).allow_offset_seconds(true);
+    assert!(Timestamp::parse_with_options("2021-10-17T02:03:01+05", &opts).is_ok());
+    assert!(Timestamp::parse_with_options("2021-10-17T02:03:01+05:00:30", &opts).is_ok());
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_reduced_precision() {
+    use iso8601_timestamp::{ParseErrorKind, ParseOptions, Precision};
+
+    let fixtures = [
+        ("2021",                    "2021-01-01T00:00:00Z",         Precision::Year),
+        ("2021-10",                 "2021-10-01T00:00:00Z",         Precision::Month),
+        ("2021-W41",                "2021-10-11T00:00:00Z",         Precision::Week),
+        ("2021W41",                 "2021-10-11T00:00:00Z",         Precision::Week),
+        ("2021-10-17",              "2021-10-17T00:00:00Z",         Precision::Day),
+        ("2021-290",                "2021-10-17T00:00:00Z",         Precision::Day),
+        ("2021-10-17T02",           "2021-10-17T02:00:00Z",         Precision::Hour),
+        ("2021-10-17T02Z",          "2021-10-17T02:00:00Z",         Precision::Hour),
+        ("2021-10-17T02+01",        "2021-10-17T01:00:00Z",         Precision::Hour),
+        ("2021-10-17T02.5",         "2021-10-17T02:30:00Z",         Precision::FractionalHour(1)),
+        ("20211017T02,25Z",         "2021-10-17T02:15:00Z",         Precision::FractionalHour(2)),
+        ("2021-10-17T02:03",        "2021-10-17T02:03:00Z",         Precision::Minute),
+        ("2021-10-17T02:03.25Z",    "2021-10-17T02:03:15Z",         Precision::FractionalMinute(2)),
+        ("2021-10-17T02:03:01",     "2021-10-17T02:03:01Z",         Precision::Second),
+        ("2021-10-17T02:03:01.120Z","2021-10-17T02:03:01.12Z",      Precision::FractionalSecond(3)),
+    ];
+
+    for (input, expected, precision) in fixtures {
+        assert_eq!(Timestamp::parse_with_precision(input), Ok((Timestamp::parse(expected).unwrap(), precision)), "{input}");
+    }
+
+    let err = |s, opts| Timestamp::parse_with_options(s, opts).unwrap_err().kind();
+
+    assert_eq!(err("202110", &ParseOptions::LENIENT), ParseErrorKind::UnexpectedEnd);
+    assert_eq!(err("2021-", &ParseOptions::LENIENT), ParseErrorKind::UnexpectedEnd);
+    assert_eq!(err("2021-13", &ParseOptions::LENIENT), ParseErrorKind::MonthOutOfRange);
+    assert_eq!(err("2021-10-17T02", &ParseOptions::RFC3339), ParseErrorKind::UnexpectedEnd);
+    assert_eq!(err("2021", &ParseOptions::RFC3339), ParseErrorKind::UnexpectedEnd);
+
+    let opts = ParseOptions::RFC3339.allow_reduced_precision(true);
+    assert_eq!(Timestamp::parse_with_options("2021-10", &opts).ok(), Timestamp::parse("2021-10-01"));
+    assert_eq!(err("2021-10-17T02Z", &opts), ParseErrorKind::UnexpectedCharacter);
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_end_of_day() {
+    use iso8601_timestamp::{ParseErrorKind, ParseOptions};
+
+    let midnight = Timestamp::parse("2021-10-18T00:00:00Z");
+
+    for fixture in ["2021-10-17T24:00", "2021-10-17T24:00:00", "2021-10-17T24:00:00.000000Z", "20211017T240000Z", "2021-10-17T24"] {
+        assert_eq!(Timestamp::parse(fixture), midnight, "{fixture}");
+    }
+
+    assert_eq!(Timestamp::parse("2021-12-31T24:00:00Z"), Timestamp::parse("2022-01-01T00:00:00Z"));
+    assert_eq!(Timestamp::parse("2021-10-17T24:00:00+01:00"), Timestamp::parse("2021-10-17T23:00:00Z"));
+    assert_eq!(Timestamp::parse_with_options("2021-10-17T24:00:00Z", &ParseOptions::XSD).ok(), midnight);
+
+    let err = |s, opts| Timestamp::parse_with_options(s, opts).unwrap_err().kind();
+
+    assert_eq!(err("2021-10-17T24:00:01Z", &ParseOptions::LENIENT), ParseErrorKind::HourOutOfRange);
+    assert_eq!(err("2021-10-17T24:30:00Z", &ParseOptions::LENIENT), ParseErrorKind::HourOutOfRange);
+    assert_eq!(err("2021-10-17T24:00:00.001Z", &ParseOptions::LENIENT), ParseErrorKind::HourOutOfRange);
+    assert_eq!(err("2021-10-17T24:00:00Z", &ParseOptions::RFC3339), ParseErrorKind::HourOutOfRange);
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_leap_seconds() {
+    use generic_array::typenum as t;
+    use iso8601_timestamp::{ParseErrorKind, ParseOptions};
+
+    // clamped by default, but still reported
+    let (ts, leap) = Timestamp::parse_with_leap_second_and_options("2016-12-31T23:59:60.5Z", &ParseOptions::LENIENT).unwrap();
+    assert_eq!((ts, leap), (Timestamp::parse("2016-12-31T23:59:59.999999999Z").unwrap(), true));
+
+    let (ts, leap) = Timestamp::parse_with_leap_second("2016-12-31T23:59:60Z").unwrap();
+    assert_eq!((ts, leap), (Timestamp::parse("2016-12-31T23:59:59Z").unwrap(), true));
+    assert_eq!(ts.format_leap_second_raw::<t::True, t::False, t::U3>(UtcOffset::UTC, leap), "2016-12-31T23:59:60.000Z");
+    assert_eq!(ts.format_leap_second_raw::<t::False, t::False, t::U0>(UtcOffset::UTC, leap), "20161231T235960Z");
+    assert_eq!(ts.format(), "2016-12-31T23:59:59.000Z");
+
+    // offsets are applied as usual, but the leap second is still the 60th second of the local minute
+    let (ts, leap) = Timestamp::parse_with_leap_second("2017-01-01T08:59:60+09:00").unwrap();
+    assert_eq!((ts, leap), (Timestamp::parse("2016-12-31T23:59:59Z").unwrap(), true));
+
+    let (ts, leap) = Timestamp::parse_with_leap_second("2016-12-31T23:59:59.999999999Z").unwrap();
+    assert_eq!((ts, leap), (Timestamp::parse("2016-12-31T23:59:59.999999999Z").unwrap(), false));
+    assert_eq!(Timestamp::parse("2016-12-31T23:59:58Z").unwrap().format_leap_second_raw::<t::True, t::False, t::U0>(UtcOffset::UTC, true), "2016-12-31T23:59:58Z");
+
+    // an ordinary last second of a minute is written as-is
+    let (ts, leap) = Timestamp::parse_with_leap_second("2021-10-17T23:59:59Z").unwrap();
+    assert!(!leap);
+    assert_eq!(ts.format_leap_second_raw::<t::True, t::False, t::U0>(UtcOffset::UTC, leap), "2021-10-17T23:59:59Z");
+
+    // leap seconds only end a UTC minute
+    assert!(Timestamp::parse_with_leap_second("2016-12-31T18:29:60-05:30").unwrap().1);
+    assert_eq!(Timestamp::parse_with_leap_second("2016-12-31T12:30:60Z").unwrap_err().kind(), ParseErrorKind::SecondOutOfRange);
+    assert_eq!(Timestamp::parse_with_leap_second("2016-12-31T23:59:60+05:30").unwrap_err().offset(), 17);
+    assert_eq!(Timestamp::parse_with_leap_second_and_options("2016-12-31T12:30:60Z", &ParseOptions::LENIENT), Ok((Timestamp::parse("2016-12-31T12:30:59.999999999Z").unwrap(), true)));
+
+    let opts = ParseOptions::LENIENT.preserve_leap_seconds(true);
+    assert_eq!(Timestamp::parse_with_options("2016-12-31T23:59:61Z", &opts).unwrap_err().kind(), ParseErrorKind::SecondOutOfRange);
+    assert_eq!(Timestamp::parse_with_options("2016-12-31T23:59:99Z", &opts).unwrap_err().kind(), ParseErrorKind::SecondOutOfRange);
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_parse_prefix() {
+    use iso8601_timestamp::{ParseErrorKind, ParseOptions};
+
+    let fixtures: &[(&[u8], &str, usize)] = &[
+        (b"2021-10-17T02:03:01.123Z INFO request completed",  "2021-10-17T02:03:01.123Z",   24),
+        (b"2021-10-17T02:03:01.123Z",                         "2021-10-17T02:03:01.123Z",   24),
+        (b"2021-10-17T02:03:01+10:00\tINFO",                  "2021-10-17T02:03:01+10:00",  25),
+        (b"2021-10-17T02:03:01+10 INFO",                      "2021-10-17T02:03:01+10:00",  22),
+        (b"2021-10-17T02:03:01UTC]",                          "2021-10-17T02:03:01Z",       22),
+        (b"2021-10-17 02:03:01 INFO",                         "2021-10-17T02:03:01Z",       19),
+        (b"20211017T020301Z|\xff\xfe",                        "2021-10-17T02:03:01Z",       16),
+        (b"2021-10-17 INFO",                                  "2021-10-17T00:00:00Z",       10),
+        (b"2021-10-17T",                                      "2021-10-17T00:00:00Z",       10),
+        (b"2021-10-17T02 INFO",                               "2021-10-17T02:00:00Z",       13),
+        (b"2021-10 INFO",                                     "2021-10-01T00:00:00Z",       7),
+        (b"2021 INFO",                                        "2021-01-01T00:00:00Z",       4),
+    ];
+
+    for &(input, expected, len) in fixtures {
+        let input_str = String::from_utf8_lossy(input);
+        assert_eq!(Timestamp::parse_prefix(input), Some((Timestamp::parse(expected).unwrap(), len)), "{input_str}");
+    }
+
+    assert_eq!(Timestamp::parse_prefix(b"INFO 2021-10-17"), None);
+    assert_eq!(Timestamp::parse_prefix(b"2021-10-17T02:03:01+1"), None);
+
+    assert_eq!(Timestamp::parse_bytes(b"2021-10-17T02:03:01Z"), Timestamp::parse("2021-10-17T02:03:01Z"));
+    assert_eq!(Timestamp::parse_bytes(b"2021-10-17T02:03:01Z\xff"), None);
+
+    let err = |s, opts| Timestamp::parse_prefix_with_options(s, opts).unwrap_err().kind();
+
+    assert!(Timestamp::parse_prefix_with_options(b"2021-10-17T02:03:01Z INFO", &ParseOptions::RFC3339).is_ok());
+    assert_eq!(err(b"2021-10-17T02:03:01 INFO", &ParseOptions::RFC3339), ParseErrorKind::UnexpectedCharacter);
+    assert_eq!(err(b"2021-10-17 INFO", &ParseOptions::RFC3339), ParseErrorKind::UnexpectedCharacter);
+    assert_eq!(Timestamp::parse_prefix_with_options(b"2021-10-17T02:03 UTC", &ParseOptions::HTML_DATETIME_LOCAL).map(|(_, len)| len), Ok(16));
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_parse_batch() {
+    let column = [
+        // a run in UTC, crossing a date
+        "2021-10-17T23:59:58.123Z",
+        "2021-10-17T23:59:59.456Z",
+        "2021-10-18T00:00:00.789Z",
+        "2021-10-18T00:00:01.000Z",
+        "2021-10-18T00:00:02.001Z",
+        // invalid values within the run
+        "2021-10-18T00:0x:03.002Z",
+        "2021-02-30T00:00:03.002Z",
+        "2021-10-18T00:00:03.0x2Z",
+        "2021-10-18T25:00:03.002Z",
+        // leap second and end of day fall back to the general parser
+        "2021-10-18T23:59:60.500Z",
+        "2021-10-18T24:00:00.000Z",
+        "",
+        "2021-10-18T00:00:04.003Z",
+        // a new layout with an offset
+        "2021-10-18 10:00:05+10:00",
+        "2021-10-18 10:00:06+10:00",
+        "2021-10-18 10:00:07-01:30",
+        "2021-10-18 10:00:08+10:00",
+        // values outside of any run keep the current one
+        "2021-W42-1",
+        "2021-10-18 10:00:09+10:00",
+        "2021-10-18T10:00:10,5",
+        "2021-10-18T10:00:11,5",
+        "2021-10-18T10:00:12,5",
+    ];
+
+    let expected: Vec<_> = column.iter().map(|s| Timestamp::parse(s)).collect();
+
+    // every length, to cover pairs and single values at the end
+    for len in 0..=column.len() {
+        let mut out = vec![None; len];
+        Timestamp::parse_batch(&column[..len], &mut out);
+        assert_eq!(out, expected[..len]);
+    }
+
+    let mut values = Vec::new();
+    let mut offsets = vec![0i64];
+    for s in column {
+        values.extend_from_slice(s.as_bytes());
+        offsets.push(values.len() as i64);
+    }
+
+    let mut out = vec![None; column.len()];
+    Timestamp::parse_batch_offsets(&offsets, &values, &mut out);
+    assert_eq!(out, expected);
+
+    // invalid offsets
+    let mut out = [Some(Timestamp::UNIX_EPOCH); 3];
+    Timestamp::parse_batch_offsets(&[0i32, 20, -1, 500], b"2021-10-17T02:03:01Z", &mut out);
+    assert_eq!(out, [Timestamp::parse("2021-10-17T02:03:01Z"), None, None]);
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_rfc2822() {
+    use iso8601_timestamp::ParseErrorKind as K;
+
+    let ts = Timestamp::parse("2021-10-17T02:03:01Z").unwrap();
+
+    let fixtures = [
+        "Sun, 17 Oct 2021 02:03:01 GMT",
+        "Sun, 17 Oct 2021 02:03:01 UT",
+        "Sun, 17 Oct 2021 02:03:01 +0000",
+        "Sun, 17 Oct 2021 02:03:01 -0000",
+        "Sun, 17 Oct 2021 02:03:01 Z",
+        "17 Oct 2021 02:03:01 GMT",
+        "sun, 17 oct 2021 02:03:01 gmt",
+        "Sun, 17 Oct 2021 12:33:01 +1030",
+        "Sat, 16 Oct 2021 19:03:01 PDT",
+        "Sat, 16 Oct 2021 18:03:01 PST",
+        "Sat, 16 Oct 2021 20:03:01 CST",
+        "Sun,  17  Oct  2021  02:03:01  GMT  ",
+        "Sun, 17 Oct 2021 02:03:01 +0000 (UTC)",
+        "Sun, 17 Oct 21 02:03:01 GMT",
+        "Sunday, 17-Oct-21 02:03:01 GMT",
+        "Sun Oct 17 02:03:01 2021",
+    ];
+
+    for fixture in fixtures {
+        assert_eq!(Timestamp::parse_rfc2822(fixture), Some(ts), "{fixture}");
+    }
+
+    // asctime pads single-digit days with a space, and the seconds are optional in RFC 2822
+    assert_eq!(Timestamp::parse_rfc2822("Sat Oct  2 02:03:01 2021"), Timestamp::parse("2021-10-02T02:03:01Z"));
+    assert_eq!(Timestamp::parse_rfc2822("2 Oct 2021 02:03 GMT"), Timestamp::parse("2021-10-02T02:03:00Z"));
+    assert_eq!(Timestamp::parse_rfc2822("Sat, 01 Jan 1994 00:00:00 GMT"), Timestamp::parse("1994-01-01T00:00:00Z"));
+    assert_eq!(Timestamp::parse_rfc2822("Sat, 01 Jan 94 00:00:00 GMT"), Timestamp::parse("1994-01-01T00:00:00Z"));
+    assert_eq!(Timestamp::parse_rfc2822("Sat, 01 Jan 094 00:00:00 GMT"), Timestamp::parse("1994-01-01T00:00:00Z"));
+
+    let errors = [
+        ("", 0, K::Empty),
+        ("Mon, 17 Oct 2021 02:03:01 GMT", 0, K::WeekdayMismatch),
+        ("Foo, 17 Oct 2021 02:03:01 GMT", 0, K::UnexpectedCharacter),
+        ("Sun, 17 Foo 2021 02:03:01 GMT", 8, K::MonthOutOfRange),
+        ("Sun, 31 Sep 2021 02:03:01 GMT", 5, K::InvalidDayForMonth),
+        ("Sun, 17 Oct 2021 24:03:01 GMT", 17, K::HourOutOfRange),
+        ("Sun, 17 Oct 2021 02:60:01 GMT", 20, K::MinuteOutOfRange),
+        ("Sun, 17 Oct 2021 02:03:61 GMT", 23, K::SecondOutOfRange),
+        ("Sun, 17 Oct 2021 02:03:01 XYZ", 26, K::InvalidOffset),
+        ("Sun, 17 Oct 2021 02:03:01 +00", 26, K::InvalidOffset),
+        ("Sun, 17 Oct 2021 02:03:01 +0060", 26, K::InvalidOffset),
+        ("Sun, 17 Oct 2021 02:03:01", 25, K::UnexpectedEnd),
+        ("Sun, 17 Oct 2021 02:03:01 GMT x", 30, K::TrailingCharacters),
+        ("Sun Oct 17 02:03 2021", 16, K::UnexpectedCharacter),
+    ];
+
+    for (fixture, offset, kind) in errors {
+        let err = Timestamp::try_parse_rfc2822(fixture).unwrap_err();
+        assert_eq!((err.offset(), err.kind()), (offset, kind), "{fixture}");
+    }
+
+    // formatting round-trips, truncating fractional seconds
+    assert_eq!(ts.format_http_date(), "Sun, 17 Oct 2021 02:03:01 GMT");
+    assert_eq!(Timestamp::parse("1994-11-06T08:49:37.999Z").unwrap().format_http_date(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    assert_eq!(Timestamp::UNIX_EPOCH.format_http_date(), "Thu, 01 Jan 1970 00:00:00 GMT");
+
+    for days in (0..20_000).step_by(7) {
+        let ts = Timestamp::UNIX_EPOCH + iso8601_timestamp::Duration::seconds(days * 86_400 + days * 131);
+        assert_eq!(Timestamp::parse_rfc2822(&ts.format_http_date()), Some(ts));
+    }
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_iso_duration() {
+    use iso8601_timestamp::{IsoDuration, ParseErrorKind as K};
+
+    let round_trips = [
+        "P1Y2M10DT2H30M", "P3W", "PT0S", "-P1D", "PT1.5S", "PT0.000000001S", "P1Y", "PT36H",
+        "P4294967295Y4294967295M4294967295W4294967295DT4294967295H4294967295M4294967295.999999999S",
+    ];
+
+    for fixture in round_trips {
+        let d = IsoDuration::parse(fixture).unwrap();
+        assert_eq!(d.format(), fixture);
+        assert_eq!(d.to_string(), fixture);
+    }
+
+    let d = IsoDuration::parse("P1Y2M10DT2H30M15.25S").unwrap();
+    assert_eq!(d, IsoDuration {
+        years: 1, months: 2, days: 10, hours: 2, minutes: 30, seconds: 15, nanoseconds: 250_000_000,
+        ..IsoDuration::ZERO
+    });
+
+    // lowercase, commas and fractional hours or minutes
+    assert_eq!(IsoDuration::parse("p1dt2h"), IsoDuration::parse("P1DT2H"));
+    assert_eq!(IsoDuration::parse("PT0,5S"), IsoDuration::parse("PT0.5S"));
+    assert_eq!(IsoDuration::parse("PT1.5H").unwrap().format(), "PT1H30M");
+    assert_eq!(IsoDuration::parse("PT0.25M").unwrap().format(), "PT15S");
+    assert_eq!(IsoDuration::parse("+P1D").unwrap().format(), "P1D");
+    assert_eq!(IsoDuration::parse("-PT0S").unwrap().format(), "PT0S");
+
+    let errors = [
+        ("", 0, K::Empty),
+        ("P", 1, K::UnexpectedEnd),
+        ("1D", 0, K::UnexpectedCharacter),
+        ("P1DT", 4, K::UnexpectedEnd),
+        ("P1", 2, K::UnexpectedEnd),
+        ("P1D1Y", 4, K::UnexpectedCharacter),
+        ("P1M1M", 4, K::UnexpectedCharacter),
+        ("PT1D", 3, K::UnexpectedCharacter),
+        ("P1.5D", 1, K::InvalidFraction),
+        ("PT1.5H1M", 6, K::TrailingCharacters),
+        ("PT1.S", 4, K::UnexpectedCharacter),
+        ("P4294967296D", 1, K::OutOfRange),
+    ];
+
+    for (fixture, offset, kind) in errors {
+        let err = IsoDuration::try_parse(fixture).unwrap_err();
+        assert_eq!((err.offset(), err.kind()), (offset, kind), "{fixture}");
+    }
+
+    // nominal components follow the calendar
+    let ts = Timestamp::parse("2021-01-31T12:00:00Z").unwrap();
+    assert_eq!(ts + IsoDuration::parse("P1M").unwrap(), Timestamp::parse("2021-02-28T12:00:00Z").unwrap());
+    assert_eq!(ts + IsoDuration::parse("P1Y1M").unwrap(), Timestamp::parse("2022-02-28T12:00:00Z").unwrap());
+    assert_eq!(ts - IsoDuration::parse("P2M").unwrap(), Timestamp::parse("2020-11-30T12:00:00Z").unwrap());
+    assert_eq!(ts + IsoDuration::parse("-P1W").unwrap(), Timestamp::parse("2021-01-24T12:00:00Z").unwrap());
+    assert_eq!(ts + IsoDuration::parse("PT12H0.5S").unwrap(), Timestamp::parse("2021-02-01T00:00:00.5Z").unwrap());
+
+    let leap = Timestamp::parse("2020-02-29T00:00:00Z").unwrap();
+    assert_eq!(leap + IsoDuration::parse("P1Y").unwrap(), Timestamp::parse("2021-02-28T00:00:00Z").unwrap());
+    assert_eq!(leap + IsoDuration::parse("P4Y").unwrap(), Timestamp::parse("2024-02-29T00:00:00Z").unwrap());
+
+    assert_eq!(IsoDuration::parse("-PT1H30M").unwrap().exact(), -iso8601_timestamp::Duration::minutes(90));
+    assert!(IsoDuration::parse("PT1H").unwrap().is_exact());
+    assert!(!IsoDuration::parse("P1D").unwrap().is_exact());
+    assert_eq!(IsoDuration::parse("P1000000Y").unwrap().checked_add_to(*ts), None);
+
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_string(&d).unwrap();
+        assert_eq!(json, r#""P1Y2M10DT2H30M15.25S""#);
+        assert_eq!(serde_json::from_str::<IsoDuration>(&json).unwrap(), d);
+        assert!(serde_json::from_str::<IsoDuration>(r#""P1X""#).is_err());
+    }
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_intervals() {
+    use iso8601_timestamp::{Interval, IsoDuration, ParseErrorKind as K, RepeatingInterval};
+
+    let ts = |s| Timestamp::parse(s).unwrap();
+    let dur = |s| IsoDuration::parse(s).unwrap();
+
+    let fixtures = [
+        ("2021-10-17T09:00:00Z/2021-10-17T17:00:00Z", Interval::StartEnd(ts("2021-10-17T09:00:00Z"), ts("2021-10-17T17:00:00Z"))),
+        ("2021-10-17T09:00:00Z/PT8H", Interval::StartDuration(ts("2021-10-17T09:00:00Z"), dur("PT8H"))),
+        ("PT8H/2021-10-17T17:00:00Z", Interval::DurationEnd(dur("PT8H"), ts("2021-10-17T17:00:00Z"))),
+        // abbreviated ends, taking the offset of the start
+        ("2021-10-17T09:00/17:00", Interval::StartEnd(ts("2021-10-17T09:00:00Z"), ts("2021-10-17T17:00:00Z"))),
+        ("2021-10-17T09:00+10:00/17:00", Interval::StartEnd(ts("2021-10-17T09:00+10:00"), ts("2021-10-17T17:00+10:00"))),
+        ("2021-10-17T09:00+10:00/17:00Z", Interval::StartEnd(ts("2021-10-17T09:00+10:00"), ts("2021-10-17T17:00Z"))),
+        ("2021-10-17T09:00Z/19T17:00", Interval::StartEnd(ts("2021-10-17T09:00Z"), ts("2021-10-19T17:00Z"))),
+        ("2021-10-17/11-02", Interval::StartEnd(ts("2021-10-17"), ts("2021-11-02"))),
+        ("2021-10-17/2021-11-02", Interval::StartEnd(ts("2021-10-17"), ts("2021-11-02"))),
+    ];
+
+    for (fixture, expected) in fixtures {
+        let interval = Interval::parse(fixture).unwrap();
+        assert_eq!(interval, expected, "{fixture}");
+        assert_eq!(Interval::parse(&interval.format()), Some(interval), "{fixture}");
+    }
+
+    let interval = Interval::parse("P1M/2021-03-31T00:00:00Z").unwrap();
+    assert_eq!(interval.format(), "P1M/2021-03-31T00:00:00.000Z");
+    assert_eq!(interval.start(), Some(ts("2021-02-28T00:00:00Z")));
+    assert_eq!(interval.end(), Some(ts("2021-03-31T00:00:00Z")));
+
+    let errors = [
+        ("", 0, K::Empty),
+        ("2021-10-17T09:00Z", 17, K::UnexpectedEnd),
+        ("2021-10-17T09:00Z/", 18, K::UnexpectedEnd),
+        ("2021-10-17T09:00Z/PT1H/PT1H", 22, K::TrailingCharacters),
+        ("PT1H/PT1H", 5, K::UnexpectedCharacter),
+        ("PT1X/2021-10-17T09:00Z", 3, K::UnexpectedCharacter),
+    ];
+
+    for (fixture, offset, kind) in errors {
+        let err = Interval::try_parse(fixture).unwrap_err();
+        assert_eq!((err.offset(), err.kind()), (offset, kind), "{fixture}");
+    }
+
+    // repeating intervals
+    let repeating = RepeatingInterval::parse("R5/2021-10-17T00:00:00Z/PT1H").unwrap();
+    assert_eq!(repeating.repetitions, Some(5));
+    assert_eq!(repeating.format(), "R5/2021-10-17T00:00:00.000Z/PT1H");
+
+    let occurrences: Vec<_> = repeating.occurrences().collect();
+    assert_eq!(occurrences.len(), 5);
+    assert_eq!(occurrences[0], ts("2021-10-17T00:00:00Z")..ts("2021-10-17T01:00:00Z"));
+    assert_eq!(occurrences[4], ts("2021-10-17T04:00:00Z")..ts("2021-10-17T05:00:00Z"));
+
+    let repeating = RepeatingInterval::parse("R/2021-10-17T09:00Z/17:00").unwrap();
+    assert_eq!(repeating.repetitions, None);
+    assert_eq!(repeating.format(), "R/2021-10-17T09:00:00.000Z/2021-10-17T17:00:00.000Z");
+    assert_eq!(repeating.into_iter().nth(3), Some(ts("2021-10-18T09:00:00Z")..ts("2021-10-18T17:00:00Z")));
+
+    // `duration/end` runs backwards from the last occurrence
+    let repeating = RepeatingInterval::parse("R3/P1M/2021-03-31T00:00:00Z").unwrap();
+    let starts: Vec<_> = repeating.occurrences().map(|r| r.start).collect();
+    assert_eq!(starts, [ts("2021-02-28T00:00:00Z"), ts("2021-01-31T00:00:00Z"), ts("2020-12-31T00:00:00Z")]);
+
+    // stops once out of range
+    assert!(RepeatingInterval::parse("R/P5000Y/2021-01-01T00:00:00Z").unwrap().occurrences().count() < 1000);
+    assert_eq!(RepeatingInterval::parse("R0/2021-01-01T00:00:00Z/P1D").unwrap().occurrences().count(), 0);
+
+    let errors = [
+        ("", 0, K::Empty),
+        ("5/2021-10-17T00:00:00Z/PT1H", 0, K::UnexpectedCharacter),
+        ("R5", 2, K::UnexpectedEnd),
+        ("R5/", 3, K::UnexpectedEnd),
+        ("R5x/PT1H", 2, K::UnexpectedCharacter),
+        ("R4294967296/2021-10-17T00:00:00Z/PT1H", 1, K::OutOfRange),
+        ("R5/2021-10-17T00:00:00Z/PT1X", 27, K::UnexpectedCharacter),
+    ];
+
+    for (fixture, offset, kind) in errors {
+        let err = RepeatingInterval::try_parse(fixture).unwrap_err();
+        assert_eq!((err.offset(), err.kind()), (offset, kind), "{fixture}");
+    }
+
+    let longest = RepeatingInterval {
+        repetitions: Some(u32::MAX),
+        interval: Interval::StartDuration(ts("-9999-01-01T00:00:00Z"), IsoDuration { negative: true, ..dur("P4294967295Y4294967295M4294967295W4294967295DT4294967295H4294967295M4294967295.999999999S") }),
+    };
+    assert_eq!(longest.format().len(), 128);
+
+    #[cfg(feature = "serde")]
+    {
+        let repeating = RepeatingInterval::parse("R5/2021-10-17T00:00:00Z/PT1H").unwrap();
+        let json = serde_json::to_string(&repeating).unwrap();
+        assert_eq!(json, r#""R5/2021-10-17T00:00:00.000Z/PT1H""#);
+        assert_eq!(serde_json::from_str::<RepeatingInterval>(&json).unwrap(), repeating);
+        assert_eq!(serde_json::from_str::<Interval>(r#""PT8H/2021-10-17T17:00:00Z""#).unwrap(), fixtures[2].1);
+    }
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_annotations() {
+    use iso8601_timestamp::{Annotation, ParseErrorKind as K, ParseOptions, typenum as t};
+
+    let ts = Timestamp::parse("2021-10-17T00:03:01Z").unwrap();
+
+    let fixtures = [
+        ("2021-10-17T02:03:01+02:00[Europe/Paris]", Some("Europe/Paris"), 1, true),
+        ("2021-10-17T02:03:01+02:00[Europe/Paris][u-ca=gregory]", Some("Europe/Paris"), 2, true),
+        ("2021-10-17T02:03:01+02:00[!Europe/Paris][!u-ca=gregory]", Some("Europe/Paris"), 2, false),
+        ("2021-10-17T02:03:01+02:00[+02:00]", Some("+02:00"), 1, true),
+        ("2021-10-17T02:03:01+02:00[!+02:00]", Some("+02:00"), 1, true),
+        ("2021-10-17T02:03:01+02:00[u-ca=gregory][_foo-1=a1-b2]", None, 2, true),
+        ("2021-10-17T00:03:01Z[America/Argentina/ComodRivadavia]", Some("America/Argentina/ComodRivadavia"), 1, true),
+        ("2021-10-17T00:03:01Z[Etc/GMT+5]", Some("Etc/GMT+5"), 1, true),
+        ("2021-10-17T00:03:01[UTC]", Some("UTC"), 1, true),
+        ("2021-10-17T02:03:01+02:00", None, 0, true),
+    ];
+
+    // critical annotations other than a matching offset can't be discarded
+    for (fixture, time_zone, count, discardable) in fixtures {
+        let (parsed, _, annotations) = Timestamp::parse_with_annotations(fixture).unwrap();
+        assert_eq!(parsed, ts, "{fixture}");
+        assert_eq!(annotations.time_zone().map(|tz| tz.value), time_zone, "{fixture}");
+        assert_eq!(annotations.iter().count(), count, "{fixture}");
+        assert_eq!(Timestamp::parse(fixture), Some(ts).filter(|_| discardable), "{fixture}");
+    }
+
+    let (_, _, annotations) = Timestamp::parse_with_annotations("2021-10-17T02:03:01+02:00[!Europe/Paris][u-ca=gregory][u-ca=iso8601]").unwrap();
+    assert_eq!(annotations.as_str(), "[!Europe/Paris][u-ca=gregory][u-ca=iso8601]");
+    assert_eq!(annotations.time_zone(), Some(Annotation::time_zone("Europe/Paris").critical()));
+    assert_eq!(annotations.get("u-ca"), Some(Annotation::tag("u-ca", "gregory")));
+    assert_eq!(annotations.get("x"), None);
+
+    let errors = [
+        ("2021-10-17T02:03:01Z[", 21, K::UnexpectedEnd),
+        ("2021-10-17T02:03:01Z[Europe/Paris", 33, K::UnexpectedEnd),
+        ("2021-10-17T02:03:01Z[]", 20, K::InvalidAnnotation),
+        ("2021-10-17T02:03:01Z[Europe/Paris]x", 34, K::TrailingCharacters),
+        ("2021-10-17T02:03:01Z[u-ca=gregory][Europe/Paris]", 34, K::InvalidAnnotation),
+        ("2021-10-17T02:03:01Z[Europe/Paris][Europe/Paris]", 34, K::InvalidAnnotation),
+        ("2021-10-17T02:03:01Z[U-CA=gregory]", 20, K::InvalidAnnotation),
+        ("2021-10-17T02:03:01Z[u-ca=]", 20, K::InvalidAnnotation),
+        ("2021-10-17T02:03:01Z[u-ca=a--b]", 20, K::InvalidAnnotation),
+        ("2021-10-17T02:03:01Z[Europe//Paris]", 20, K::InvalidAnnotation),
+        ("2021-10-17T02:03:01Z[Europe/..]", 20, K::InvalidAnnotation),
+        ("2021-10-17T02:03:01Z[Abcdefghijklmno]", 20, K::InvalidAnnotation),
+        ("2021-10-17T02:03:01+02:00[!+03:00]", 25, K::InvalidAnnotation),
+        ("2021-13-17T02:03:01Z[Europe/Paris]", 5, K::MonthOutOfRange),
+    ];
+
+    for (fixture, offset, kind) in errors {
+        let err = Timestamp::parse_with_annotations(fixture).unwrap_err();
+        assert_eq!((err.offset(), err.kind()), (offset, kind), "{fixture}");
+    }
+
+    let fixture = "2021-10-17T02:03:01Z[!u-ca=hebrew]";
+    assert_eq!(Timestamp::parse(fixture), None);
+    let err = Timestamp::parse_with_options(fixture, &ParseOptions::RFC9557).unwrap_err();
+    assert_eq!((err.offset(), err.kind()), (20, K::InvalidAnnotation));
+    assert_eq!(Timestamp::parse_with_annotations_and_options(fixture, &ParseOptions::RFC9557).unwrap().2.get("u-ca"), Some(Annotation::tag("u-ca", "hebrew").critical()));
+
+    // an elective offset annotation may disagree
+    assert!(Timestamp::parse("2021-10-17T02:03:01+02:00[+03:00]").is_some());
+
+    // strict profiles opt in
+    let fixture = "2021-10-17T02:03:01+02:00[Europe/Paris]";
+    assert_eq!(Timestamp::parse_with_options(fixture, &ParseOptions::RFC3339).unwrap_err().kind(), K::TrailingCharacters);
+    assert_eq!(Timestamp::parse_with_options(fixture, &ParseOptions::RFC9557), Ok(ts));
+    assert_eq!(Timestamp::parse_with_options("2021-10-17T02:03:01[Europe/Paris]", &ParseOptions::RFC9557).unwrap_err().kind(), K::UnexpectedEnd);
+    assert_eq!(Timestamp::parse_with_options(fixture, &ParseOptions::LENIENT.allow_annotations(false)).unwrap_err().kind(), K::TrailingCharacters);
+
+    // prefixes stop before any annotations
+    assert_eq!(Timestamp::parse_prefix(fixture.as_bytes()), Some((ts, 25)));
+
+    // formatting
+    let (parsed, offset, annotations) = Timestamp::parse_with_annotations(fixture).unwrap();
+    assert_eq!(parsed.format_at_offset::<t::U0>(offset).with_annotations(annotations).to_string(), fixture);
+
+    let annotations = [Annotation::time_zone("Europe/Paris").critical(), Annotation::tag("u-ca", "gregory")];
+    assert_eq!(
+        ts.format().with_annotations(annotations).to_string(),
+        "2021-10-17T00:03:01.000Z[!Europe/Paris][u-ca=gregory]",
+    );
+    assert_eq!(ts.format().with_annotations(annotations.iter().copied().skip(1)).to_string(), "2021-10-17T00:03:01.000Z[u-ca=gregory]");
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_trimmed_fractions() {
+    use iso8601_timestamp::{TrimFraction, UtcOffset, typenum as t};
+
+    let base = Timestamp::parse("2021-10-17T02:03:01Z").unwrap();
+
+    for nanos in [0, 1, 10, 100, 120_000_000, 123_000_000, 123_400_000, 123_456_000, 123_456_700, 999_999_999, 500_000_000] {
+        let ts = base + iso8601_timestamp::Duration::nanoseconds(nanos);
+
+        // trimming the full nanosecond format by hand
+        let full = ts.format_nanoseconds();
+        let fraction = full[20..29].trim_end_matches('0');
+
+        let expected = match fraction.len() {
+            0 => "2021-10-17T02:03:01Z".to_owned(),
+            _ => format!("2021-10-17T02:03:01.{fraction}Z"),
+        };
+        assert_eq!(ts.format_trimmed(), &*expected);
+        assert_eq!(Timestamp::parse(&ts.format_trimmed()), Some(ts));
+
+        let digits = fraction.len().div_ceil(3) * 3;
+        let expected = match digits {
+            0 => "2021-10-17T02:03:01Z".to_owned(),
+            _ => format!("2021-10-17T02:03:01.{}Z", &full[20..20 + digits]),
+        };
+        assert_eq!(ts.format_trimmed_to_groups(), &*expected);
+    }
+
+    let ts = Timestamp::parse("-0005-10-17T02:03:01.5Z").unwrap();
+    let offset = UtcOffset::from_hms(-5, -30, 0).unwrap();
+
+    assert_eq!(ts.format_trimmed(), "-0005-10-17T02:03:01.5Z");
+    assert_eq!(ts.format_trimmed_raw::<t::True, t::True, t::U3>(offset, TrimFraction::Zeros), "-0005-10-17T02:03:01.5-05:30");
+    assert_eq!(ts.format_trimmed_raw::<t::True, t::True, t::U0>(offset, TrimFraction::Zeros), "-0005-10-17T02:03:01-05:30");
+    assert_eq!(ts.format_trimmed_raw::<t::False, t::True, t::U6>(offset, TrimFraction::Groups), "-00051017T020301.500-0530");
+    assert_eq!(ts.format_trimmed_raw::<t::False, t::False, t::U2>(UtcOffset::UTC, TrimFraction::Groups), "-00051017T020301.50Z");
+    assert_eq!(ts.format_trimmed_raw::<t::True, t::False, t::U1>(UtcOffset::UTC, TrimFraction::Zeros), "-0005-10-17T02:03:01.5Z");
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_format_spec() {
+    use iso8601_timestamp::{FormatSpec, TrimFraction, UtcOffset, typenum as t};
+
+    let fixtures = ["2021-10-17T02:03:01.123456789Z", "-0005-01-01T00:00:00Z", "9999-12-31T23:59:59.999999999Z", "2021-10-17T02:03:01.1Z"];
+
+    for fixture in fixtures {
+        let ts = Timestamp::parse(fixture).unwrap();
+
+        // every spec matches the typed format with the same parameters
+        macro_rules! check {
+            ($($p:ty),*) => {$(
+                let precision = <$p as t::Unsigned>::U8;
+                let spec = FormatSpec::DEFAULT.precision(precision);
+
+                assert_eq!(ts.format_spec(&spec), &*ts.format_raw::<t::True, t::False, $p>(UtcOffset::UTC));
+                assert_eq!(ts.format_spec(&spec.utc_offset(true)), &*ts.format_raw::<t::True, t::True, $p>(UtcOffset::UTC));
+                assert_eq!(ts.format_spec(&spec.basic_format(true)), &*ts.format_raw::<t::False, t::False, $p>(UtcOffset::UTC));
+                assert_eq!(ts.format_spec(&spec.basic_format(true).utc_offset(true)), &*ts.format_raw::<t::False, t::True, $p>(UtcOffset::UTC));
+
+                for trim in [TrimFraction::Zeros, TrimFraction::Groups] {
+                    assert_eq!(
+                        ts.format_spec(&spec.trim(Some(trim))),
+                        &*ts.format_trimmed_raw::<t::True, t::False, $p>(UtcOffset::UTC, trim),
+                    );
+                    assert_eq!(
+                        ts.format_spec(&spec.basic_format(true).utc_offset(true).trim(Some(trim))),
+                        &*ts.format_trimmed_raw::<t::False, t::True, $p>(UtcOffset::UTC, trim),
+                    );
+                }
+            )*};
+        }
+
+        check!(t::U0, t::U1, t::U2, t::U3, t::U4, t::U5, t::U6, t::U7, t::U8, t::U9);
+    }
+
+    let ts = Timestamp::parse("2021-10-17T02:03:01.120Z").unwrap();
+
+    assert_eq!(ts.format_spec(&FormatSpec::default()), "2021-10-17T02:03:01.120Z");
+    assert_eq!(ts.format_spec(&FormatSpec::DEFAULT.space_separator(true).precision(6)), "2021-10-17 02:03:01.120000Z");
+    assert_eq!(ts.format_spec(&FormatSpec::DEFAULT.decimal_comma(true)), "2021-10-17T02:03:01,120Z");
+    assert_eq!(ts.format_spec(&FormatSpec::DEFAULT.decimal_comma(true).precision(9).trim(Some(TrimFraction::Zeros))), "2021-10-17T02:03:01,12Z");
+    assert_eq!(ts.format_spec(&FormatSpec::DEFAULT.precision(0).space_separator(true).utc_offset(true)), "2021-10-17 02:03:01+00:00");
+    assert_eq!(ts.format_spec(&FormatSpec::DEFAULT.precision(200)), &*ts.format_nanoseconds());
+
+    for spec in [FormatSpec::DEFAULT.space_separator(true), FormatSpec::DEFAULT.decimal_comma(true).precision(9)] {
+        assert_eq!(Timestamp::parse(&ts.format_spec(&spec)), Some(ts));
+    }
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_write_to() {
+    use iso8601_timestamp::typenum as t;
+    use time::macros::offset;
+
+    let fixtures = ["2021-10-17T02:03:01.123456789Z", "-0005-01-01T00:00:00Z", "9999-12-31T23:59:59.999999999Z"];
+
+    for fixture in fixtures {
+        let ts = Timestamp::parse(fixture).unwrap();
+
+        macro_rules! check {
+            ($f:ty, $o:ty, $p:ty, $offset:expr) => {{
+                let expected = ts.format_raw::<$f, $o, $p>($offset);
+
+                let mut buf = [0xFF; 64];
+                let len = ts.write_to_slice::<$f, $o, $p>($offset, &mut buf).unwrap();
+                assert_eq!(&buf[..len], expected.as_bytes());
+
+                // too short for the template, even when the output would fit
+                let max_len = expected.len() + !fixture.starts_with('-') as usize;
+                assert_eq!(ts.write_to_slice::<$f, $o, $p>($offset, &mut buf[..max_len - 1]), None);
+
+                let mut s = String::from("ts=");
+                ts.write_to_fmt::<$f, $o, $p, _>($offset, &mut s).unwrap();
+                assert_eq!(s, format!("ts={expected}"));
+
+                #[cfg(feature = "std")]
+                {
+                    let mut v = b"ts=".to_vec();
+                    ts.write_to_io::<$f, $o, $p, _>($offset, &mut v).unwrap();
+                    assert_eq!(v, s.as_bytes());
+                }
+
+                #[cfg(feature = "bytes")]
+                {
+                    let mut v = b"ts=".to_vec();
+                    ts.write_to_buf::<$f, $o, $p, _>($offset, &mut v);
+                    assert_eq!(v, s.as_bytes());
+
+                    // not enough contiguous space, so falls back to copying
+                    let mut buf = [0u8; 64];
+                    let mut rest = &mut buf[..expected.len()];
+                    ts.write_to_buf::<$f, $o, $p, _>($offset, &mut rest);
+                    assert!(rest.is_empty());
+                    assert_eq!(&buf[..expected.len()], expected.as_bytes());
+                }
+            }};
+        }
+
+        check!(t::True, t::False, t::U3, UtcOffset::UTC);
+        check!(t::True, t::True, t::U9, offset!(-04:30));
+        check!(t::False, t::False, t::U0, UtcOffset::UTC);
+        check!(t::False, t::True, t::U6, offset!(+02:00));
+    }
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_named_formats() {
+    use iso8601_timestamp::typenum as t;
+    use time::macros::offset;
+
+    let ts = Timestamp::parse("2021-10-17T02:03:01.123456789Z").unwrap();
+    let offset = offset!(-04:30);
+
+    assert_eq!(ts.format_short_with_precision::<t::U0>(), "20211017T020301Z");
+    assert_eq!(ts.format_short_with_precision::<t::U6>(), "20211017T020301.123456Z");
+    assert_eq!(ts.format_short_with_offset(offset), "20211017T020301.123-0430");
+    assert_eq!(ts.format_short_with_offset_and_precision::<t::U9>(offset), "20211017T020301.123456789-0430");
+    assert_eq!(ts.format_date(), "2021-10-17");
+    assert_eq!(ts.format_short_date(), "20211017");
+    assert_eq!(Timestamp::parse("-0005-01-01T00:00:00Z").unwrap().format_date(), "-0005-01-01");
+
+    // basic format offsets parse back to the same timestamp
+    let local = Timestamp::parse("2021-10-16T21:33:01.123Z").unwrap();
+    assert_eq!(Timestamp::parse(&local.format_short_with_offset(offset)), Timestamp::parse("2021-10-17T02:03:01.123Z"));
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_display() {
+    let ts = Timestamp::parse("2021-10-17T02:03:01.123456789Z").unwrap();
+
+    assert_eq!(format!("{ts}"), &*ts.format());
+    assert_eq!(ts.to_string(), "2021-10-17T02:03:01.123Z");
+    assert_eq!(format!("{ts:.0}"), "2021-10-17T02:03:01Z");
+    assert_eq!(format!("{ts:.9}"), "2021-10-17T02:03:01.123456789Z");
+    assert_eq!(format!("{ts:.20}"), "2021-10-17T02:03:01.123456789Z");
+    assert_eq!(format!("{ts:.*}", 2), "2021-10-17T02:03:01.12Z");
+    assert_eq!(format!("{ts:#}"), "20211017T020301.123Z");
+    assert_eq!(format!("{ts:#.6}"), "20211017T020301.123456Z");
+
+    assert_eq!(format!("[{ts:28}]"), "[2021-10-17T02:03:01.123Z    ]");
+    assert_eq!(format!("[{ts:>28}]"), "[    2021-10-17T02:03:01.123Z]");
+    assert_eq!(format!("[{ts:*^29}]"), "[**2021-10-17T02:03:01.123Z***]");
+    assert_eq!(format!("[{ts:-<#20.0}]"), "[20211017T020301Z----]");
+    assert_eq!(format!("[{ts:5}]"), "[2021-10-17T02:03:01.123Z]");
+
+    let negative = Timestamp::parse("-0005-01-01T00:00:00Z").unwrap();
+    assert_eq!(format!("{negative:#.0}"), "-00050101T000000Z");
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_formatted() {
+    use iso8601_timestamp::{formats, typenum as t, FormatString, Formatted, TimestampMicros, TimestampSeconds, TimestampNanos};
+
+    let ts = Timestamp::parse("2021-10-17T02:03:01.123456789Z").unwrap();
+
+    assert_eq!(TimestampSeconds::new(ts).to_string(), "2021-10-17T02:03:01Z");
+    assert_eq!(TimestampMicros::from(ts).to_string(), "2021-10-17T02:03:01.123456Z");
+    assert_eq!(Formatted::<formats::ShortMillisecondsOffset>::new(ts).to_string(), "20211017T020301.123+0000");
+    assert_eq!(format!("{:?}", TimestampNanos::new(ts)), r#"Formatted("2021-10-17T02:03:01.123456789Z")"#);
+    assert_eq!(Timestamp::from(TimestampMicros::new(ts)), ts);
+
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_string(&TimestampMicros::new(ts)).unwrap();
+        assert_eq!(json, r#""2021-10-17T02:03:01.123456Z""#);
+        assert_eq!(*serde_json::from_str::<TimestampMicros>(&json).unwrap(), Timestamp::parse("2021-10-17T02:03:01.123456Z").unwrap());
+        assert_eq!(*serde_json::from_str::<TimestampSeconds>("1634436181").unwrap(), Timestamp::parse("2021-10-17T02:03:01Z").unwrap());
+
+        // binary formats use the coarsest integer unit that keeps the precision
+        fn binary<T: serde::Serialize>(value: T) -> (i64, Vec<u8>) {
+            let mut buf = Vec::new();
+            ciborium::ser::into_writer(&value, &mut buf).unwrap();
+            (ciborium::de::from_reader(&buf[..]).unwrap(), buf)
+        }
+
+        assert_eq!(binary(TimestampSeconds::new(ts)).0, 1634436181);
+        assert_eq!(binary(Formatted::<formats::FullMicrosecondsOffset>::new(ts)).0, 1634436181123456);
+        assert_eq!(binary(TimestampNanos::new(ts)).0, 1634436181123456789);
+
+        let before_epoch = Timestamp::parse("1969-12-31T23:59:59.5Z").unwrap();
+        let (ms, buf) = binary(Formatted::<FormatString<t::True, t::False, t::U1>>::new(before_epoch));
+        assert_eq!(ms, -500);
+        assert_eq!(*ciborium::de::from_reader::<TimestampMicros, _>(&buf[..]).unwrap(), Timestamp::parse("1969-12-31T23:59:59.9995Z").unwrap());
+        assert_eq!(ciborium::de::from_reader::<Formatted, _>(&buf[..]).unwrap().into_inner(), before_epoch);
+
+        // nanoseconds only fit in an i64 until 2262
+        let mut buf = Vec::new();
+        assert!(ciborium::ser::into_writer(&TimestampNanos::new(Timestamp::parse("2300-01-01T00:00:00Z").unwrap()), &mut buf).is_err());
+    }
+}
+
+#[cfg(feature = "serde")]
+#[rustfmt::skip]
+#[test]
+fn test_serde_with() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Fields {
+        #[serde(with = "iso8601_timestamp::serde::unix_seconds")]
+        seconds: Timestamp,
+        #[serde(with = "iso8601_timestamp::serde::unix_millis")]
+        millis: Timestamp,
+        #[serde(with = "iso8601_timestamp::serde::unix_micros")]
+        micros: Timestamp,
+        #[serde(with = "iso8601_timestamp::serde::unix_nanos")]
+        nanos: Timestamp,
+        #[serde(with = "iso8601_timestamp::serde::float_seconds")]
+        float: Timestamp,
+        #[serde(with = "iso8601_timestamp::serde::string")]
+        string: Timestamp,
+        #[serde(with = "iso8601_timestamp::serde::option_string", default)]
+        option_string: Option<Timestamp>,
+        #[serde(with = "iso8601_timestamp::serde::option_unix_millis", default)]
+        option_millis: Option<Timestamp>,
+        #[serde(with = "iso8601_timestamp::serde::option_float_seconds", default)]
+        option_float: Option<Timestamp>,
+    }
+
+    let ts = Timestamp::parse("2021-10-17T02:03:01.123456789Z").unwrap();
+    let ms = Timestamp::parse("2021-10-17T02:03:01.123Z").unwrap();
+
+    let fields = Fields {
+        seconds: ts, millis: ts, micros: ts, nanos: ts, float: ms, string: ms,
+        option_string: Some(ms), option_millis: None, option_float: Some(ms),
+    };
+
+    let json = serde_json::to_string(&fields).unwrap();
+    assert_eq!(json, concat!(
+        r#"{"seconds":1634436181,"millis":1634436181123,"micros":1634436181123456,"nanos":1634436181123456789,"#,
+        r#""float":1634436181.123,"string":"2021-10-17T02:03:01.123Z","option_string":"2021-10-17T02:03:01.123Z","#,
+        r#""option_millis":null,"option_float":1634436181.123}"#,
+    ));
+
+    let truncated = Fields {
+        seconds: Timestamp::parse("2021-10-17T02:03:01Z").unwrap(),
+        millis: ms,
+        micros: Timestamp::parse("2021-10-17T02:03:01.123456Z").unwrap(),
+        ..fields
+    };
+
+    assert_eq!(serde_json::from_str::<Fields>(&json).unwrap(), truncated);
+
+    // binary formats use the same encodings, including strings
+    let mut buf = Vec::new();
+    ciborium::ser::into_writer(&fields, &mut buf).unwrap();
+    assert_eq!(ciborium::de::from_reader::<Fields, _>(&buf[..]).unwrap(), truncated);
+
+    // empty strings and missing fields are `None`
+    let partial: Fields = serde_json::from_str(r#"{"seconds":0,"millis":0,"micros":-1,"nanos":0,"float":-0.5,"string":"1970-01-01T00:00:00Z","option_string":""}"#).unwrap();
+    assert_eq!(partial.micros, Timestamp::parse("1969-12-31T23:59:59.999999Z").unwrap());
+    assert_eq!(partial.float, Timestamp::parse("1969-12-31T23:59:59.5Z").unwrap());
+    assert_eq!((partial.option_string, partial.option_millis, partial.option_float), (None, None, None));
+
+    let none = Fields { option_string: None, option_float: None, ..truncated };
+    assert!(serde_json::to_string(&none).unwrap().contains(r#""option_string":"","option_millis":null,"option_float":null"#));
+
+    assert!(serde_json::from_str::<Fields>(r#"{"seconds":"2021-10-17T02:03:01Z"}"#).is_err());
+    assert!(serde_json::from_str::<Fields>(r#"{"seconds":18446744073709551615}"#).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[rustfmt::skip]
+#[test]
+fn test_serde_lossless() {
+    use iso8601_timestamp::serde::Lossless;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Cached {
+        #[serde(with = "iso8601_timestamp::serde::lossless")]
+        ts: Timestamp,
+        #[serde(with = "iso8601_timestamp::serde::option_lossless", default)]
+        expires: Option<Timestamp>,
+        history: Vec<Lossless>,
+    }
+
+    fn cbor<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut buf = Vec::new();
+        ciborium::ser::into_writer(value, &mut buf).unwrap();
+        buf
+    }
+
+    for fixture in ["2021-10-17T02:03:01.123456789Z", "1969-12-31T23:59:59.999999999Z", "-9999-01-01T00:00:00.000000001Z", "9999-12-31T23:59:59.999999999Z"] {
+        let ts = Timestamp::parse(fixture).unwrap();
+        let cached = Cached { ts, expires: Some(ts), history: vec![Lossless(ts), Lossless::from(Timestamp::UNIX_EPOCH)] };
+
+        let buf = cbor(&cached);
+        assert_eq!(ciborium::de::from_reader::<Cached, _>(&buf[..]).unwrap(), cached);
+
+        let json = serde_json::to_string(&cached).unwrap();
+        assert!(json.starts_with(&format!(r#"{{"ts":"{}","#, ts.format_nanoseconds())));
+        assert_eq!(serde_json::from_str::<Cached>(&json).unwrap(), cached);
+
+        // the regular encoding reads the lossless one too
+        assert_eq!(ciborium::de::from_reader::<Timestamp, _>(&cbor(&Lossless(ts))[..]).unwrap(), ts);
+    }
+
+    // binary formats write whole seconds and the nanoseconds within them
+    let before_epoch = Lossless(Timestamp::parse("1969-12-31T23:59:59.25Z").unwrap());
+    assert_eq!(ciborium::de::from_reader::<(i64, u32), _>(&cbor(&before_epoch)[..]).unwrap(), (-1, 250_000_000));
+
+    assert!(ciborium::de::from_reader::<Lossless, _>(&cbor(&(0i64, 1_000_000_000u32))[..]).is_err());
+    assert!(ciborium::de::from_reader::<Timestamp, _>(&cbor(&(i64::MAX, 0u32))[..]).is_err());
+
+    // and the default milliseconds in self-describing formats
+    let ts = Timestamp::parse("2021-10-17T02:03:01.123Z").unwrap();
+    assert_eq!(serde_json::from_str::<Lossless>("1634436181123").unwrap().0, ts);
+    assert_eq!(ciborium::de::from_reader::<Lossless, _>(&cbor(&ts)[..]).unwrap().0, ts);
+
+    #[derive(Serialize)]
+    struct Old { ts: Timestamp, expires: Option<Timestamp>, history: Vec<Timestamp> }
+
+    let old = cbor(&Old { ts, expires: Some(ts), history: vec![ts] });
+    assert_eq!(ciborium::de::from_reader::<Cached, _>(&old[..]).unwrap(), Cached { ts, expires: Some(ts), history: vec![Lossless(ts)] });
+
+    // or only the tuple, for formats that are not self-describing
+    let mut de = serde_json::Deserializer::from_str("[1634436181,123000000]");
+    assert_eq!(iso8601_timestamp::serde::lossless::deserialize_tuple(&mut de).unwrap(), ts);
+    assert_eq!(serde_json::from_str::<Cached>(r#"{"ts":"2021-10-17T02:03:01Z","history":[]}"#).unwrap().expires, None);
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_ordinal_dates() {
+    use generic_array::typenum as t;
+
+    let ts = Timestamp::parse("2021-10-17T02:03:01Z").unwrap();
+
+    for fixture in ["2021-290T02:03:01Z", "2021290T020301Z", "2021290T02:03:01Z", "2021-290T02:03:01+00:00"] {
+        assert_eq!(Timestamp::parse(fixture), Some(ts), "{fixture}");
+    }
+
+    assert_eq!(Timestamp::parse("2021-290"), Timestamp::parse("2021-10-17"));
+    assert_eq!(Timestamp::parse("2021290"), Timestamp::parse("2021-10-17"));
+    assert_eq!(Timestamp::parse("2020-366"), Timestamp::parse("2020-12-31"));
+    assert_eq!(Timestamp::parse("-0004-351T10:00:00Z"), Timestamp::parse("-0004-12-16T10:00:00Z"));
+
+    let err = Timestamp::try_parse("2021-366").unwrap_err();
+    assert_eq!((err.offset(), err.kind()), (5, iso8601_timestamp::ParseErrorKind::OrdinalOutOfRange));
+
+    assert_eq!(ts.format_ordinal(), "2021-290T02:03:01.000Z");
+    assert_eq!(ts.format_ordinal_raw::<t::True, t::False, t::U0>(UtcOffset::UTC), "2021-290T02:03:01Z");
+    assert_eq!(ts.format_ordinal_raw::<t::False, t::False, t::U9>(UtcOffset::UTC), "2021290T020301.000000000Z");
+    assert_eq!(ts.format_ordinal_raw::<t::True, t::True, t::U3>(UtcOffset::from_hms(-4, -30, 0).unwrap()), "2021-290T02:03:01.000-04:30");
+    assert_eq!(ts.format_ordinal_raw::<t::False, t::True, t::U0>(UtcOffset::UTC), "2021290T020301+0000");
+
+    let neg = iso8601_timestamp::datetime!(-0004-01-05 10:00 AM);
+    assert_eq!(neg.format_ordinal(), "-0004-005T10:00:00.000Z");
+
+    for fixture in ["2021-290T02:03:01Z", "2021290T020301Z"] {
+        assert!(Timestamp::parse_with_options(fixture, &iso8601_timestamp::ParseOptions::RFC3339).is_err());
+    }
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_week_dates() {
+    use generic_array::typenum as t;
+    use iso8601_timestamp::ParseErrorKind as K;
+
+    let ts = Timestamp::parse("2021-10-17T02:03:01Z").unwrap();
+
+    for fixture in ["2021-W41-7T02:03:01Z", "2021W417T020301Z", "2021-w41-7t02:03:01z"] {
+        assert_eq!(Timestamp::parse(fixture), Some(ts), "{fixture}");
+    }
+
+    assert_eq!(ts.format_week(), "2021-W41-7T02:03:01.000Z");
+    assert_eq!(ts.format_week_raw::<t::True, t::False, t::U0>(UtcOffset::UTC), "2021-W41-7T02:03:01Z");
+    assert_eq!(ts.format_week_raw::<t::False, t::False, t::U0>(UtcOffset::UTC), "2021W417T020301Z");
+    assert_eq!(ts.format_week_raw::<t::True, t::True, t::U6>(UtcOffset::from_hms(5, 30, 0).unwrap()), "2021-W41-7T02:03:01.000000+05:30");
+
+    // week-numbering year differs from the calendar year at the boundaries
+    let boundaries = [
+        ("2021-01-01", "2020-W53-5"),
+        ("2021-01-03", "2020-W53-7"),
+        ("2021-01-04", "2021-W01-1"),
+        ("2019-12-30", "2020-W01-1"),
+        ("2024-12-30", "2025-W01-1"),
+        ("2026-12-31", "2026-W53-4"),
+    ];
+
+    for (calendar, week) in boundaries {
+        let ts = Timestamp::parse(calendar).unwrap();
+
+        assert_eq!(Timestamp::parse(week), Some(ts), "{week}");
+        assert!(ts.format_week().starts_with(week), "{calendar} -> {}", ts.format_week());
+    }
+
+    let kind = |s| Timestamp::try_parse(s).unwrap_err().kind();
+
+    assert_eq!(kind("2021-W53-1"), K::WeekOutOfRange);
+    assert_eq!(kind("2021-W00-1"), K::WeekOutOfRange);
+    assert_eq!(kind("2021-W41-8"), K::WeekdayOutOfRange);
+    assert_eq!(kind("2021-W41-0"), K::WeekdayOutOfRange);
+
+    assert!(Timestamp::parse_with_options("2021-W41-7T02:03:01Z", &iso8601_timestamp::ParseOptions::RFC3339).is_err());
+}
+
+#[cfg(feature = "large-dates")]
+#[rustfmt::skip]
+#[test]
+fn test_expanded_years() {
+    use generic_array::typenum as t;
+    use iso8601_timestamp::ParseOptions;
+
+    let opts = ParseOptions::LENIENT.expanded_year_digits(2).check_digits(true);
+
+    let fixtures = [
+        ("+012021-10-17T02:03:01Z", "+012021-10-17T02:03:01.000Z"),
+        ("-012021-10-17T02:03:01Z", "-012021-10-17T02:03:01.000Z"),
+        ("+0120211017T020301Z", "+012021-10-17T02:03:01.000Z"),
+        ("+999999-12-31T23:59:59Z", "+999999-12-31T23:59:59.000Z"),
+        ("+002021-290", "+002021-10-17T00:00:00.000Z"),
+        ("2021-10-17T02:03:01Z", "+002021-10-17T02:03:01.000Z"),
+    ];
+
+    for (input, expected) in fixtures {
+        let ts = Timestamp::parse_with_options(input, &opts).unwrap();
+
+        assert_eq!(ts.format_expanded(), expected);
+        assert_eq!(Timestamp::parse_with_options(&ts.format_expanded(), &opts), Ok(ts));
+    }
+
+    // the agreed number of digits is required for signed years
+    assert!(Timestamp::parse_with_options("+2021-10-17", &opts).is_err());
+    assert!(Timestamp::parse_with_options("+12021-10-17", &opts).is_err());
+    assert!(Timestamp::parse_with_options("+12021-10-17", &ParseOptions::LENIENT.expanded_year_digits(1)).is_ok());
+
+    let ts = Timestamp::parse_with_options("+012021-10-17T02:03:01Z", &opts).unwrap();
+
+    assert_eq!(ts.format_expanded_raw::<t::False, t::False, t::U0, t::U1>(UtcOffset::UTC), "+120211017T020301Z");
+    assert_eq!(ts.format_expanded_raw::<t::True, t::True, t::U0, t::U2>(UtcOffset::UTC), "+012021-10-17T02:03:01+00:00");
+
+    // fixed-width formats saturate years that do not fit
+    let neg = Timestamp::parse_with_options("-012021-10-17T02:03:01Z", &opts).unwrap();
+
+    assert_eq!(ts.format(), "9999-12-31T23:59:59.999Z");
+    assert_eq!(neg.format(), "-9999-01-01T00:00:00.000Z");
+    assert_eq!(ts.format_week(), "9999-W52-7T23:59:59.999Z");
+    assert_eq!(neg.format_week(), "-9999-W01-1T00:00:00.000Z");
+    let big = Timestamp::parse_with_options("+123456-10-17T02:03:01Z", &opts).unwrap();
+    assert_eq!(big.format_expanded_raw::<t::True, t::False, t::U0, t::U1>(UtcOffset::UTC), "+99999-12-31T23:59:59Z");
+    assert_eq!(ts.format_http_date(), "Fri, 31 Dec 9999 23:59:59 GMT");
+    assert_eq!(neg.format_http_date(), "Sat, 01 Jan 0000 00:00:00 GMT");
+
+    // while debug, display and serde write them in full
+    assert_eq!(format!("{ts:?}"), r#"Timestamp("+012021-10-17T02:03:01.000000000Z")"#);
+    assert_eq!(format!("{neg:.0}"), "-012021-10-17T02:03:01.000000000Z");
+
+    // as do intervals, which read them back
+    use iso8601_timestamp::{Interval, IsoDuration, RepeatingInterval};
+
+    let interval = Interval::StartEnd(neg, ts);
+    assert_eq!(interval.format(), "-012021-10-17T02:03:01.000000000Z/+012021-10-17T02:03:01.000000000Z");
+    assert_eq!(Interval::parse(&interval.format()), Some(interval));
+
+    let longest = RepeatingInterval {
+        repetitions: Some(u32::MAX),
+        interval: Interval::StartDuration(neg, IsoDuration { negative: true, ..IsoDuration::parse("P4294967295Y4294967295M4294967295W4294967295DT4294967295H4294967295M4294967295.999999999S").unwrap() }),
+    };
+    assert_eq!(longest.format().len(), 136);
+}
+
+#[cfg(feature = "large-dates")]
+#[test]
+#[should_panic]
+fn test_expanded_year_digits_out_of_range() {
+    let _ = iso8601_timestamp::ParseOptions::LENIENT.expanded_year_digits(3);
+}
+
+#[cfg(all(feature = "large-dates", feature = "serde"))]
+#[rustfmt::skip]
+#[test]
+fn test_expanded_years_serde() {
+    use iso8601_timestamp::{ParseOptions, TimestampNanos};
+
+    let opts = ParseOptions::LENIENT.expanded_year_digits(2);
+
+    for fixture in ["+012021-10-17T02:03:01.123456789Z", "-012021-10-17T02:03:01.123456789Z", "+999999-12-31T23:59:59.999999999Z"] {
+        let ts = Timestamp::parse_with_options(fixture, &opts).unwrap();
+        let json = serde_json::to_string(&ts).unwrap();
+
+        assert_eq!(json, format!("\"{fixture}\""));
+        assert_eq!(serde_json::from_str::<Timestamp>(&json).unwrap(), ts);
+
+        let formatted = TimestampNanos::new(ts);
+        assert_eq!(serde_json::from_str::<TimestampNanos>(&serde_json::to_string(&formatted).unwrap()).unwrap(), formatted);
+    }
 }